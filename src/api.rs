@@ -1,15 +1,31 @@
 //! This contains all the Stuff to interact with the DigitalOcean API
 
-use std::fmt::Debug;
+use std::{
+    fmt::Debug,
+    path::Path,
+    sync::{Mutex, RwLock},
+    time::Instant,
+};
 
 use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+
+use crate::metrics::requests;
 
 /// The API Instance to interact with the Digital Ocean API as a given User
 pub struct API {
-    /// The API Key used to authenticate with the API
-    key: ApiKey,
+    /// The Name of the Account this API Instance belongs to, which is attached to all its Metrics
+    name: String,
+    /// The API Key used to authenticate with the API, this can be swapped out while running
+    key: RwLock<ApiKey>,
+    /// The Base-URL against which all the Requests are made
+    base_url: String,
     /// The Reqwest Client used to perform all these Requests
     client: reqwest::Client,
+    /// The last Rate-Limit information received from the API
+    rate_limit: Mutex<Option<RateLimit>>,
+    /// The Metrics about the Requests made by this Instance
+    metrics: requests::Metrics,
 }
 
 /// This represents a single API Key for the DigitalOcean API
@@ -29,8 +45,32 @@ impl From<&str> for ApiKey {
     }
 }
 
-/// The Base API-Url for the DigitalOcean API
-const BASE_URL: &str = "https://api.digitalocean.com/v2";
+impl ApiKey {
+    /// Reads the Key from the given File, ignoring any leading or trailing Whitespace
+    pub fn from_file<P>(path: P) -> std::io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let content = std::fs::read_to_string(path)?;
+        Ok(Self::from(content.trim()))
+    }
+}
+
+/// The Number of Items requested per Page when loading a List of Resources, this is the maximum
+/// allowed by the DigitalOcean API
+const PAGE_SIZE: usize = 200;
+
+mod builder;
+pub use builder::{
+    APIBuilder, BuildError, DEFAULT_BASE_URL, DEFAULT_CONNECT_TIMEOUT, DEFAULT_NAME,
+    DEFAULT_TIMEOUT, DEFAULT_USER_AGENT,
+};
+
+mod key;
+pub use key::watch_key_file;
+
+mod ratelimit;
+pub use ratelimit::RateLimit;
 
 mod resources;
 pub use resources::*;
@@ -66,6 +106,36 @@ pub enum GetResouceError {
     MissingData,
     /// The Data could not be deserlazed properly
     Serde(serde_json::Error),
+    /// The Number of loaded Items does not match the Total reported by the API
+    TotalMismatch {
+        /// The Total Number of Items reported by the API
+        expected: u64,
+        /// The Number of Items that were actually loaded
+        received: u64,
+    },
+    /// Some of the Requests for the Details of the Resources failed, the Metrics have only been
+    /// updated with the Details that could be loaded
+    Partial {
+        /// The Number of Requests that failed
+        failed: usize,
+        /// The Error of the first failed Request
+        first: Box<GetResouceError>,
+    },
+}
+
+impl GetResouceError {
+    /// Combines the Errors of the Requests for the Details of the Resources, returning Ok if
+    /// none of them failed
+    pub(crate) fn partial(errors: Vec<Self>) -> Result<(), Self> {
+        let failed = errors.len();
+        match errors.into_iter().next() {
+            Some(first) => Err(Self::Partial {
+                failed,
+                first: Box::new(first),
+            }),
+            None => Ok(()),
+        }
+    }
 }
 
 impl From<GetError> for GetResouceError {
@@ -80,19 +150,54 @@ impl From<serde_json::Error> for GetResouceError {
 }
 
 impl API {
-    /// This creates a new API Instance with the given API-Key
+    /// This creates a new API Instance with the given API-Key and the default Configuration
     pub fn new<I>(key: I) -> Self
     where
         I: Into<ApiKey>,
     {
-        let client = reqwest::Client::new();
-        Self {
-            key: key.into(),
-            client,
-        }
+        Self::builder(key)
+            .build()
+            .expect("The default Configuration should always be valid")
+    }
+
+    /// Creates a Builder to configure the API Instance for the given API-Key, like the Base-URL,
+    /// Timeouts or a Proxy
+    pub fn builder<I>(key: I) -> APIBuilder
+    where
+        I: Into<ApiKey>,
+    {
+        APIBuilder::new(key)
+    }
+
+    /// The Name of the Account this API Instance belongs to
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The Metrics about the Requests made by this Instance
+    pub(crate) fn metrics(&self) -> &requests::Metrics {
+        &self.metrics
+    }
+
+    /// Replaces the API-Key used for all following Requests
+    pub fn set_key<I>(&self, key: I)
+    where
+        I: Into<ApiKey>,
+    {
+        *self.key.write().unwrap() = key.into();
+    }
+
+    /// The last Rate-Limit information received from the API, if any Request has been made so
+    /// far
+    pub fn rate_limit(&self) -> Option<RateLimit> {
+        *self.rate_limit.lock().unwrap()
     }
 
-    /// Simply attempts to load the given Resource from the DigitalOcean API
+    /// Simply attempts to load the given Resource from the DigitalOcean API.
+    ///
+    /// Requests that fail because of the Rate-Limit or a Server-Error are retried with an
+    /// exponential Backoff and once the remaining Requests run low, Requests are delayed to
+    /// spread them out until the Limit resets.
     #[tracing::instrument(skip(self))]
     pub async fn get<I>(&self, resource: I) -> Result<serde_json::Value, GetError>
     where
@@ -105,26 +210,120 @@ impl API {
             }
             tmp
         };
-        let url = format!("{}{}", BASE_URL, resource);
+        let url = format!("{}{}", self.base_url, resource);
+        let endpoint = endpoint_template(&resource);
 
-        let req = self
-            .client
-            .request(reqwest::Method::GET, url)
-            .bearer_auth(&self.key.key)
-            .build()?;
+        let mut attempt = 0;
+        let response = loop {
+            if let Some(delay) = self.rate_limit().and_then(|r| r.throttle_delay()) {
+                tracing::warn!("Running low on API-Requests, throttling for {:?}", delay);
+                tokio::time::sleep(delay).await;
+            }
 
-        let response = self.client.execute(req).await?;
-        if response.status() != reqwest::StatusCode::OK {
-            return Err(GetError::StatusCode(response.status()));
-        }
+            let key = self.key.read().unwrap().key.clone();
+            let req = self
+                .client
+                .request(reqwest::Method::GET, &url)
+                .bearer_auth(key)
+                .build()?;
+
+            let start = Instant::now();
+            let response = match self.client.execute(req).await {
+                Ok(r) => r,
+                Err(e) => {
+                    self.metrics
+                        .observe_request(&endpoint, "error", start.elapsed());
+                    return Err(e.into());
+                }
+            };
+            if let Some(rate_limit) = RateLimit::from_headers(response.headers()) {
+                self.metrics.observe_rate_limit(&rate_limit);
+                *self.rate_limit.lock().unwrap() = Some(rate_limit);
+            }
+
+            let status = response.status();
+            self.metrics
+                .observe_request(&endpoint, status.as_str(), start.elapsed());
+            if status == reqwest::StatusCode::OK {
+                break response;
+            }
+            if !ratelimit::is_retryable(status) || attempt >= ratelimit::MAX_RETRIES {
+                return Err(GetError::StatusCode(status));
+            }
+
+            let delay = ratelimit::backoff(attempt);
+            tracing::warn!(
+                "Request failed with {}, retrying in {:?} (Attempt {}/{})",
+                status,
+                delay,
+                attempt + 1,
+                ratelimit::MAX_RETRIES
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        };
 
         let raw_body = response.bytes().await?;
+        self.metrics
+            .observe_response_size(&endpoint, raw_body.len());
 
         let body = serde_json::from_slice(&raw_body)?;
 
         Ok(body)
     }
 
+    /// Loads every Item of a List-Resource from the API by following all of its Pages.
+    ///
+    /// The `key` is the Name of the Field in the Response that contains the List of Items.
+    /// Once the last Page has been loaded, the Number of Items is validated against the
+    /// `meta.total` reported by the API.
+    #[tracing::instrument(skip(self))]
+    pub async fn get_list<T>(&self, resource: &str, key: &str) -> Result<Vec<T>, GetResouceError>
+    where
+        T: DeserializeOwned,
+    {
+        let separator = if resource.contains('?') { '&' } else { '?' };
+
+        let mut result = Vec::new();
+        let mut total = None;
+        let mut page = 1;
+        loop {
+            let paged_resource = format!(
+                "{}{}page={}&per_page={}",
+                resource, separator, page, PAGE_SIZE
+            );
+            let mut raw_body = self.get(paged_resource).await?;
+
+            let raw_items = raw_body
+                .get_mut(key)
+                .map(serde_json::Value::take)
+                .ok_or(GetResouceError::MissingData)?;
+            let items: Vec<T> = serde_json::from_value(raw_items)?;
+
+            if let Some(page_total) = raw_body.pointer("/meta/total") {
+                total = page_total.as_u64();
+            }
+
+            let has_next = raw_body.pointer("/links/pages/next").is_some();
+            let empty_page = items.is_empty();
+            result.extend(items);
+
+            if !has_next || empty_page {
+                break;
+            }
+            page += 1;
+        }
+
+        if let Some(expected) = total {
+            let received = result.len() as u64;
+            if expected != received {
+                return Err(GetResouceError::TotalMismatch { expected, received });
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Simply loads the given Resource from the API
     pub async fn load_resource<R>(&self) -> Result<R::LoadData, GetResouceError>
     where
@@ -134,6 +333,28 @@ impl API {
     }
 }
 
+/// Turns the Path of a Resource into a Template, by removing the Query and replacing all the
+/// Segments that identify a specific Resource (IDs, UUIDs and Domain-Names) with `{id}`, to get
+/// a bounded Set of Endpoints for the Metrics
+fn endpoint_template(resource: &str) -> String {
+    let path = resource.split('?').next().unwrap_or_default();
+
+    path.split('/')
+        .map(|segment| {
+            let is_number = !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit());
+            let is_uuid = segment.len() == 36 && segment.matches('-').count() == 4;
+            let is_domain = segment.contains('.');
+
+            if is_number || is_uuid || is_domain {
+                "{id}"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 /// Defines a simple Interface to load a Resource from the API
 #[async_trait]
 pub trait APIRessource {
@@ -144,3 +365,59 @@ pub trait APIRessource {
     /// Loads the Resource from the API
     async fn load(api: &API) -> Result<Self::LoadData, GetResouceError>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_static_paths() {
+        assert_eq!(endpoint_template("/account"), "/account");
+        assert_eq!(
+            endpoint_template("/customers/my/balance"),
+            "/customers/my/balance"
+        );
+        assert_eq!(
+            endpoint_template("/monitoring/metrics/droplet/load_1"),
+            "/monitoring/metrics/droplet/load_1"
+        );
+    }
+
+    #[test]
+    fn strips_query() {
+        assert_eq!(
+            endpoint_template("/droplets?page=2&per_page=200"),
+            "/droplets"
+        );
+        assert_eq!(
+            endpoint_template("/monitoring/metrics/droplet/cpu?host_id=123&start=1&end=2"),
+            "/monitoring/metrics/droplet/cpu"
+        );
+    }
+
+    #[test]
+    fn replaces_ids() {
+        assert_eq!(endpoint_template("/droplets/123456"), "/droplets/{id}");
+        assert_eq!(
+            endpoint_template("/kubernetes/clusters/bd5f5959-5e1e-4205-a714-a914373942af/upgrades"),
+            "/kubernetes/clusters/{id}/upgrades"
+        );
+        assert_eq!(
+            endpoint_template("/domains/example.com/records?page=1"),
+            "/domains/{id}/records"
+        );
+        assert_eq!(
+            endpoint_template("/databases/9cc10173-e9ea-4176-9dbc-a4cee4c4ff30/replicas"),
+            "/databases/{id}/replicas"
+        );
+    }
+
+    #[test]
+    fn keeps_segments_similar_to_ids() {
+        // Only purely numeric Segments and Segments in the Format of a UUID are replaced
+        assert_eq!(endpoint_template("/v2/droplets"), "/v2/droplets");
+        assert_eq!(endpoint_template("/1-click"), "/1-click");
+        assert_eq!(endpoint_template("/"), "/");
+        assert_eq!(endpoint_template(""), "");
+    }
+}
@@ -0,0 +1,218 @@
+//! Allows for a more detailed Configuration of the API-Client
+
+use std::{
+    path::PathBuf,
+    sync::{Mutex, RwLock},
+    time::Duration,
+};
+
+use super::{ApiKey, API};
+use crate::metrics::requests;
+
+/// The Base API-Url for the DigitalOcean API
+pub const DEFAULT_BASE_URL: &str = "https://api.digitalocean.com/v2";
+
+/// The Account-Name that will be used if no other one is configured
+pub const DEFAULT_NAME: &str = "default";
+
+/// The User-Agent that will be used if no other one is configured
+pub const DEFAULT_USER_AGENT: &str = concat!("rustyocean/", env!("CARGO_PKG_VERSION"));
+
+/// The Timeout for establishing a Connection that will be used if no other one is configured
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The Timeout for an entire Request that will be used if no other one is configured
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The Error returned when the API could not be built from the given Configuration
+#[derive(Debug)]
+pub enum BuildError {
+    /// The Base-URL is not a valid URL
+    InvalidBaseUrl(String),
+    /// The CA-Bundle could not be read
+    ReadCaBundle(std::io::Error),
+    /// The CA-Bundle did not contain any Certificates
+    EmptyCaBundle,
+    /// Configuring the underlying HTTP-Client failed
+    Reqwest(reqwest::Error),
+}
+
+impl From<reqwest::Error> for BuildError {
+    fn from(e: reqwest::Error) -> Self {
+        Self::Reqwest(e)
+    }
+}
+
+/// A Builder to configure and create an API Instance
+pub struct APIBuilder {
+    /// The Name of the Account, used to distinguish the Metrics of multiple Accounts
+    name: String,
+    /// The API Key used to authenticate with the API
+    key: ApiKey,
+    /// The Base-URL against which all the Requests are made
+    base_url: String,
+    /// The User-Agent send with every Request
+    user_agent: String,
+    /// The Timeout for establishing a Connection
+    connect_timeout: Duration,
+    /// The Timeout for an entire Request
+    timeout: Duration,
+    /// The URL of the Proxy to use for all Requests
+    proxy: Option<String>,
+    /// The Path to a File containing additional trusted Certificates
+    ca_bundle: Option<PathBuf>,
+}
+
+impl APIBuilder {
+    /// Creates a new Builder for the given API-Key with the default Configuration
+    pub fn new<I>(key: I) -> Self
+    where
+        I: Into<ApiKey>,
+    {
+        Self {
+            name: DEFAULT_NAME.to_string(),
+            key: key.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            timeout: DEFAULT_TIMEOUT,
+            proxy: None,
+            ca_bundle: None,
+        }
+    }
+
+    /// Sets the Name of the Account, which is attached as the `account` Label to all the Metrics
+    /// loaded through this API Instance
+    pub fn name<S>(mut self, name: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the Base-URL against which all the Requests are made, this defaults to the public
+    /// DigitalOcean API
+    pub fn base_url<S>(mut self, url: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.base_url = url.into();
+        self
+    }
+
+    /// Sets the User-Agent that is send with every Request
+    pub fn user_agent<S>(mut self, agent: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.user_agent = agent.into();
+        self
+    }
+
+    /// Sets the Timeout for establishing a Connection to the API, this defaults to 10 Seconds
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Sets the Timeout for an entire Request, from connecting until the Response-Body has been
+    /// received, this defaults to 30 Seconds
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sends all Requests through the given HTTP(S)-Proxy
+    pub fn proxy<S>(mut self, url: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Trusts all the PEM-encoded Certificates in the given File, in addition to the System's
+    /// Root-Certificates
+    pub fn ca_bundle<P>(mut self, path: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.ca_bundle = Some(path.into());
+        self
+    }
+
+    /// Creates the API Instance using the current Configuration
+    pub fn build(self) -> Result<API, BuildError> {
+        let base_url = self.base_url.trim_end_matches('/').to_string();
+        if reqwest::Url::parse(&base_url).is_err() {
+            return Err(BuildError::InvalidBaseUrl(base_url));
+        }
+
+        let mut client = reqwest::Client::builder()
+            .user_agent(self.user_agent)
+            .connect_timeout(self.connect_timeout)
+            .timeout(self.timeout);
+        if let Some(proxy) = self.proxy {
+            client = client.proxy(reqwest::Proxy::all(proxy)?);
+        }
+        if let Some(path) = self.ca_bundle {
+            for cert in load_ca_bundle(&path)? {
+                client = client.add_root_certificate(cert);
+            }
+        }
+
+        Ok(API {
+            metrics: requests::Metrics::new(&self.name),
+            name: self.name,
+            key: RwLock::new(self.key),
+            base_url,
+            client: client.build()?,
+            rate_limit: Mutex::new(None),
+        })
+    }
+}
+
+/// Loads all the PEM-encoded Certificates from the given File, ignoring any other Content like
+/// Comments between them
+fn load_ca_bundle(path: &std::path::Path) -> Result<Vec<reqwest::Certificate>, BuildError> {
+    let content = std::fs::read(path).map_err(BuildError::ReadCaBundle)?;
+
+    let certs = rustls_pemfile::certs(&mut content.as_slice())
+        .map_err(BuildError::ReadCaBundle)?
+        .iter()
+        .map(|der| reqwest::Certificate::from_der(der))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if certs.is_empty() {
+        return Err(BuildError::EmptyCaBundle);
+    }
+    Ok(certs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_ca_bundle_with_comments_and_crlf() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("fixtures")
+            .join("ca-bundle.pem");
+
+        let certs = load_ca_bundle(&path).unwrap();
+
+        assert_eq!(certs.len(), 2);
+    }
+
+    #[test]
+    fn rejects_empty_ca_bundle() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+
+        assert!(matches!(
+            load_ca_bundle(&path),
+            Err(BuildError::EmptyCaBundle)
+        ));
+    }
+}
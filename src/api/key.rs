@@ -0,0 +1,87 @@
+//! Keeps the API-Key up to date, when it is loaded from a File that is rotated
+
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use super::{ApiKey, API};
+
+/// Notifies about external Requests to reload the API-Key, which is a SIGHUP on Unix
+struct ReloadSignal {
+    #[cfg(unix)]
+    hangup: Option<tokio::signal::unix::Signal>,
+}
+
+impl ReloadSignal {
+    fn new() -> Self {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+
+            let hangup = match signal(SignalKind::hangup()) {
+                Ok(s) => Some(s),
+                Err(e) => {
+                    tracing::warn!("Listening for SIGHUP: {:?}", e);
+                    None
+                }
+            };
+            Self { hangup }
+        }
+        #[cfg(not(unix))]
+        {
+            Self {}
+        }
+    }
+
+    /// Waits until the File should be checked again, which is either after the Interval has
+    /// passed or once a Reload has been requested
+    async fn wait(&mut self, interval: Duration) {
+        #[cfg(unix)]
+        if let Some(hangup) = self.hangup.as_mut() {
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => {}
+                _ = hangup.recv() => {
+                    tracing::info!("Received SIGHUP, reloading the API-Key");
+                }
+            }
+            return;
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// This will run forever and periodically (or on SIGHUP) re-read the API-Key from the given
+/// File, replacing the Key of the API whenever it changed.
+///
+/// This allows for rotating the Key without restarting the Process, for example when it is
+/// mounted from a Kubernetes-Secret.
+#[tracing::instrument(skip(api))]
+pub async fn watch_key_file(api: Arc<API>, path: PathBuf, interval: Duration) {
+    let mut current = match ApiKey::from_file(&path) {
+        Ok(k) => k.key,
+        Err(e) => {
+            tracing::error!("Reading API-Key from {:?}: {:?}", path, e);
+            String::new()
+        }
+    };
+
+    let mut reload = ReloadSignal::new();
+    loop {
+        reload.wait(interval).await;
+
+        let key = match ApiKey::from_file(&path) {
+            Ok(k) => k,
+            Err(e) => {
+                // Keep using the old Key, the File might just be in the Process of being replaced
+                tracing::error!("Reading API-Key from {:?}: {:?}", path, e);
+                continue;
+            }
+        };
+        if key.key.is_empty() || key.key == current {
+            continue;
+        }
+
+        tracing::info!("The API-Key in {:?} changed, using the new Key", path);
+        current = key.key.clone();
+        api.set_key(key);
+    }
+}
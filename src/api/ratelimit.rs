@@ -0,0 +1,164 @@
+//! Handles the Rate-Limit of the DigitalOcean API
+
+use std::{
+    convert::TryFrom,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use rand::Rng;
+
+/// The Number of times a single Request will be retried before giving up
+pub(crate) const MAX_RETRIES: u32 = 5;
+/// The Delay before the first Retry, this is doubled for every following Retry
+const BASE_DELAY: Duration = Duration::from_millis(500);
+/// The maximum Delay between two Retries
+const MAX_DELAY: Duration = Duration::from_secs(60);
+/// The Fraction of the Limit below which Requests will be spread out over the remaining Time
+/// until the Limit resets, instead of being sent right away
+const THROTTLE_THRESHOLD: f64 = 0.1;
+
+/// The Rate-Limit information returned by the DigitalOcean API with every Response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    /// The Number of Requests that can be made per Hour
+    pub limit: u64,
+    /// The Number of Requests that are left until the Limit resets
+    pub remaining: u64,
+    /// The Unix-Timestamp (in seconds) at which the Limit resets
+    pub reset: u64,
+}
+
+impl RateLimit {
+    /// Attempts to parse the Rate-Limit from the `ratelimit-*` Headers of a Response
+    pub fn from_headers(headers: &reqwest::header::HeaderMap) -> Option<Self> {
+        let parse = |name: &str| -> Option<u64> { headers.get(name)?.to_str().ok()?.parse().ok() };
+
+        Some(Self {
+            limit: parse("ratelimit-limit")?,
+            remaining: parse("ratelimit-remaining")?,
+            reset: parse("ratelimit-reset")?,
+        })
+    }
+
+    /// The Time that is left until the Limit resets
+    fn until_reset(&self) -> Duration {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Duration::from_secs(self.reset.saturating_sub(now))
+    }
+
+    /// The Time that should be waited before sending the next Request, to avoid running out of
+    /// Requests before the Limit resets
+    pub fn throttle_delay(&self) -> Option<Duration> {
+        let threshold = (self.limit as f64 * THROTTLE_THRESHOLD) as u64;
+        if self.remaining > threshold {
+            return None;
+        }
+
+        let until_reset = self.until_reset();
+        if until_reset.is_zero() {
+            return None;
+        }
+
+        match self.remaining {
+            0 => Some(until_reset),
+            remaining => Some(until_reset / u32::try_from(remaining).unwrap_or(u32::MAX)),
+        }
+    }
+}
+
+/// Calculates the jittered exponential Delay before the given Retry-Attempt (starting at 0)
+pub(crate) fn backoff(attempt: u32) -> Duration {
+    let exponential = BASE_DELAY
+        .checked_mul(2u32.saturating_pow(attempt))
+        .unwrap_or(MAX_DELAY)
+        .min(MAX_DELAY);
+
+    // Randomize the Delay to avoid multiple Clients retrying in lockstep
+    let jittered = rand::thread_rng().gen_range(0.5..=1.0) * exponential.as_secs_f64();
+    Duration::from_secs_f64(jittered)
+}
+
+/// Whether or not a Request that failed with the given StatusCode should be retried
+pub(crate) fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate_limit(limit: u64, remaining: u64, reset_in: u64) -> RateLimit {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        RateLimit {
+            limit,
+            remaining,
+            reset: now + reset_in,
+        }
+    }
+
+    #[test]
+    fn backoff_grows_and_is_capped() {
+        for attempt in 0..4 {
+            let exponential = BASE_DELAY * 2u32.pow(attempt);
+            let delay = backoff(attempt);
+            assert!(delay >= exponential / 2 && delay <= exponential);
+        }
+        for attempt in [10, 31, 32, u32::MAX] {
+            let delay = backoff(attempt);
+            assert!(delay >= MAX_DELAY / 2 && delay <= MAX_DELAY);
+        }
+    }
+
+    #[test]
+    fn retries_only_rate_limits_and_server_errors() {
+        assert!(is_retryable(reqwest::StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable(reqwest::StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable(reqwest::StatusCode::UNAUTHORIZED));
+        assert!(!is_retryable(reqwest::StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn throttles_only_below_threshold() {
+        assert_eq!(rate_limit(5000, 4000, 600).throttle_delay(), None);
+
+        let delay = rate_limit(5000, 100, 600).throttle_delay().unwrap();
+        assert!(delay <= Duration::from_secs(6));
+
+        let delay = rate_limit(5000, 0, 600).throttle_delay().unwrap();
+        assert!(delay > Duration::from_secs(590));
+    }
+
+    #[test]
+    fn does_not_throttle_after_reset() {
+        let expired = RateLimit {
+            limit: 5000,
+            remaining: 0,
+            reset: 0,
+        };
+        assert_eq!(expired.throttle_delay(), None);
+    }
+
+    #[test]
+    fn parses_headers() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("ratelimit-limit", "5000".parse().unwrap());
+        headers.insert("ratelimit-remaining", "12".parse().unwrap());
+        assert_eq!(RateLimit::from_headers(&headers), None);
+
+        headers.insert("ratelimit-reset", "1700000000".parse().unwrap());
+        assert_eq!(
+            RateLimit::from_headers(&headers),
+            Some(RateLimit {
+                limit: 5000,
+                remaining: 12,
+                reset: 1700000000
+            })
+        );
+    }
+}
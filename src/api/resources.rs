@@ -1,5 +1,7 @@
 use super::{APIRessource, GetResouceError, API};
 
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use serde::Deserialize;
 
@@ -44,8 +46,11 @@ impl APIRessource for Account {
 /// Represents the Balance of a given Account
 #[derive(Debug, Deserialize)]
 pub struct Balance {
+    /// The current Balance of the Account
     pub account_balance: String,
+    /// The Time at which this Balance has been generated
     pub generated_at: String,
+    /// The Balance including the Usage of this Month
     pub month_to_date_balance: String,
     /// The current Usage in this Month
     pub month_to_date_usage: String,
@@ -64,7 +69,7 @@ impl APIRessource for Balance {
 }
 
 /// Represents a single Droplet
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Droplet {
     /// The ID to uniquely identify a Droplet
     pub id: u64,
@@ -76,6 +81,7 @@ pub struct Droplet {
     pub vcpus: u64,
     /// The specified Disk Size assigned to the Droplet
     pub disk: u64,
+    /// Whether or not the Droplet is currently locked, preventing any Actions on it
     pub locked: bool,
     /// The current Status of the Droplet
     pub status: DropletStatus,
@@ -85,23 +91,78 @@ pub struct Droplet {
     pub size: DropletSize,
     /// The Region in which the Droplet exists
     pub region: Region,
+    /// The Networks the Droplet is connected to
+    pub networks: DropletNetworks,
+    /// The Tags that have been applied to the Droplet
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The ID of the VPC the Droplet belongs to
+    #[serde(default)]
+    pub vpc_uuid: Option<String>,
+}
+
+/// The Networks a Droplet is connected to
+#[derive(Debug, Clone, Deserialize)]
+pub struct DropletNetworks {
+    /// All the IPv4 Networks of the Droplet
+    #[serde(default)]
+    pub v4: Vec<NetworkV4>,
+}
+
+impl DropletNetworks {
+    /// Returns the first IPv4 Address of the given Network-Type
+    pub fn ipv4(&self, ty: NetworkType) -> Option<&str> {
+        self.v4
+            .iter()
+            .find(|n| n.ty == ty)
+            .map(|n| n.ip_address.as_str())
+    }
+}
+
+/// A single IPv4 Network-Interface of a Droplet
+#[derive(Debug, Clone, Deserialize)]
+pub struct NetworkV4 {
+    /// The IP-Address of the Droplet in this Network
+    pub ip_address: String,
+    /// The Netmask of the Network
+    pub netmask: String,
+    /// The Gateway of the Network
+    pub gateway: String,
+    /// Whether this is a public or private Network
+    #[serde(rename = "type")]
+    pub ty: NetworkType,
+}
+
+/// The Type of a Network
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum NetworkType {
+    /// The Network is reachable from the Internet
+    #[serde(rename = "public")]
+    Public,
+    /// The Network is only reachable from inside the VPC
+    #[serde(rename = "private")]
+    Private,
 }
 
 /// The Status of a Droplet
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub enum DropletStatus {
+    /// The Droplet has just been created and is still being set up
     #[serde(rename = "new")]
     New,
+    /// The Droplet is up and running
     #[serde(rename = "active")]
     Active,
+    /// The Droplet is powered off
     #[serde(rename = "off")]
     Off,
+    /// The Droplet has been archived
     #[serde(rename = "archive")]
     Archive,
 }
 
 /// Information about a Droplets specified Size
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct DropletSize {
     /// The Slug used to identify a Droplet-Size
     pub slug: String,
@@ -131,20 +192,14 @@ impl APIRessource for Droplets {
     type LoadData = Vec<Droplet>;
 
     async fn load(api: &API) -> Result<Self::LoadData, GetResouceError> {
-        let raw_body = api.get("/droplets").await?;
-
-        let raw_droplets = raw_body
-            .get("droplets")
-            .ok_or(GetResouceError::MissingData)?;
-
-        let droplets = serde_json::from_value(raw_droplets.clone())?;
+        let droplets = api.get_list("/droplets", "droplets").await?;
 
         Ok(droplets)
     }
 }
 
 /// Represents a Region
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct Region {
     /// The Name of the Region
     pub name: String,
@@ -169,13 +224,7 @@ impl APIRessource for FloatingIps {
     type LoadData = Vec<FloatingIp>;
 
     async fn load(api: &API) -> Result<Self::LoadData, GetResouceError> {
-        let raw_body = api.get("/floating_ips").await?;
-
-        let raw_floating_ips = raw_body
-            .get("floating_ips")
-            .ok_or(GetResouceError::MissingData)?;
-
-        let floating_ips = serde_json::from_value(raw_floating_ips.clone())?;
+        let floating_ips = api.get_list("/floating_ips", "floating_ips").await?;
 
         Ok(floating_ips)
     }
@@ -196,6 +245,7 @@ pub struct VPC {
     pub default: bool,
     /// The ID of the VPC
     pub id: String,
+    /// The Uniform Resource Name of the VPC
     pub urn: String,
     /// The Time of creation
     pub created_at: String,
@@ -209,11 +259,7 @@ impl APIRessource for VPCs {
     type LoadData = Vec<VPC>;
 
     async fn load(api: &API) -> Result<Self::LoadData, GetResouceError> {
-        let raw_body = api.get("/vpcs").await?;
-
-        let raw_vpcs = raw_body.get("vpcs").ok_or(GetResouceError::MissingData)?;
-
-        let vpcs = serde_json::from_value(raw_vpcs.clone())?;
+        let vpcs = api.get_list("/vpcs", "vpcs").await?;
 
         Ok(vpcs)
     }
@@ -224,11 +270,15 @@ impl APIRessource for VPCs {
 pub struct CdnEndpoint {
     /// The ID of the Endpoint
     pub id: String,
+    /// The Origin-Server from which the Content is pulled
     pub origin: String,
+    /// The Hostname under which the Content is served
     pub endpoint: String,
     /// The TTL for the Content of this CDN-Endpoint
     pub ttl: u64,
+    /// The ID of the Certificate used for the Custom-Domain
     pub certificate_id: String,
+    /// The Custom-Domain under which the Content is served
     pub custom_domain: String,
     /// The Time at which this was created
     pub created_at: String,
@@ -242,14 +292,578 @@ impl APIRessource for CdnEndpoints {
     type LoadData = Vec<CdnEndpoint>;
 
     async fn load(api: &API) -> Result<Self::LoadData, GetResouceError> {
-        let raw_body = api.get("/cdn/endpoints").await?;
+        let endpoints = api.get_list("/cdn/endpoints", "endpoints").await?;
+
+        Ok(endpoints)
+    }
+}
+
+/// Represents a single Block-Storage Volume
+#[derive(Debug, Deserialize)]
+pub struct Volume {
+    /// The ID of the Volume
+    pub id: String,
+    /// The Name of the Volume
+    pub name: String,
+    /// The Region in which the Volume exists
+    pub region: Region,
+    /// The IDs of the Droplets the Volume is attached to
+    #[serde(default)]
+    pub droplet_ids: Vec<u64>,
+    /// The Size of the Volume in GiB
+    pub size_gigabytes: u64,
+    /// The Type of Filesystem on the Volume, empty if it has not been formatted
+    #[serde(default)]
+    pub filesystem_type: String,
+    /// The Time at which this was created
+    pub created_at: String,
+}
+
+/// Represents a List of Volumes that can be loaded from the API
+pub struct Volumes {}
+
+#[async_trait]
+impl APIRessource for Volumes {
+    type LoadData = Vec<Volume>;
+
+    async fn load(api: &API) -> Result<Self::LoadData, GetResouceError> {
+        let volumes = api.get_list("/volumes", "volumes").await?;
+
+        Ok(volumes)
+    }
+}
+
+/// Represents a single Kubernetes-Cluster (DOKS)
+#[derive(Debug, Deserialize)]
+pub struct KubernetesCluster {
+    /// The ID of the Cluster
+    pub id: String,
+    /// The Name of the Cluster
+    pub name: String,
+    /// The Slug of the Region in which the Cluster exists
+    pub region: String,
+    /// The Slug of the Kubernetes-Version running on the Cluster
+    pub version: String,
+    /// Whether or not the Cluster will be upgraded automatically
+    pub auto_upgrade: bool,
+    /// Whether or not the Control-Plane is highly available
+    #[serde(default)]
+    pub ha: bool,
+    /// The current Status of the Cluster
+    pub status: KubernetesStatus,
+    /// The Node-Pools that belong to the Cluster
+    pub node_pools: Vec<KubernetesNodePool>,
+    /// The Time at which this was created
+    pub created_at: String,
+}
+
+/// The Status of a Kubernetes-Cluster or Node
+#[derive(Debug, Deserialize)]
+pub struct KubernetesStatus {
+    /// The State of the Resource, like "running", "provisioning" or "degraded"
+    pub state: String,
+    /// An optional Message explaining the current State
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Represents a single Node-Pool of a Kubernetes-Cluster
+#[derive(Debug, Deserialize)]
+pub struct KubernetesNodePool {
+    /// The ID of the Node-Pool
+    pub id: String,
+    /// The Name of the Node-Pool
+    pub name: String,
+    /// The Slug of the Droplet-Size used for the Nodes
+    pub size: String,
+    /// The Number of Nodes in the Node-Pool
+    pub count: u64,
+    /// Whether or not the Node-Pool is automatically scaled
+    #[serde(default)]
+    pub auto_scale: bool,
+    /// The minimum Number of Nodes when auto scaling
+    #[serde(default)]
+    pub min_nodes: u64,
+    /// The maximum Number of Nodes when auto scaling
+    #[serde(default)]
+    pub max_nodes: u64,
+    /// The Nodes that currently belong to the Node-Pool
+    #[serde(default)]
+    pub nodes: Vec<KubernetesNode>,
+}
+
+/// Represents a single Node in a Node-Pool
+#[derive(Debug, Deserialize)]
+pub struct KubernetesNode {
+    /// The ID of the Node
+    pub id: String,
+    /// The Name of the Node
+    pub name: String,
+    /// The current Status of the Node
+    pub status: KubernetesStatus,
+    /// The ID of the Droplet backing the Node
+    #[serde(default)]
+    pub droplet_id: String,
+}
+
+/// Represents a List of Kubernetes-Clusters that can be loaded from the API
+pub struct KubernetesClusters {}
+
+#[async_trait]
+impl APIRessource for KubernetesClusters {
+    type LoadData = Vec<KubernetesCluster>;
+
+    async fn load(api: &API) -> Result<Self::LoadData, GetResouceError> {
+        let clusters = api
+            .get_list("/kubernetes/clusters", "kubernetes_clusters")
+            .await?;
+
+        Ok(clusters)
+    }
+}
+
+/// A Kubernetes-Version to which a Cluster can be upgraded
+#[derive(Debug, Deserialize)]
+pub struct KubernetesUpgrade {
+    /// The Slug of the Version
+    pub slug: String,
+    /// The Kubernetes-Version
+    pub kubernetes_version: String,
+}
+
+/// Represents the List of available Upgrades for a single Kubernetes-Cluster
+pub struct KubernetesUpgrades {}
+
+impl KubernetesUpgrades {
+    /// Loads the available Upgrades for the Cluster with the given ID
+    pub async fn load_for(
+        api: &API,
+        cluster_id: &str,
+    ) -> Result<Vec<KubernetesUpgrade>, GetResouceError> {
+        let raw_body = api
+            .get(format!("/kubernetes/clusters/{}/upgrades", cluster_id))
+            .await?;
+
+        // The API returns null instead of an empty List, if no Upgrades are available
+        let raw_upgrades = match raw_body.get("available_upgrade_versions") {
+            Some(serde_json::Value::Null) => return Ok(Vec::new()),
+            Some(u) => u,
+            None => return Err(GetResouceError::MissingData),
+        };
+
+        let upgrades = serde_json::from_value(raw_upgrades.clone())?;
+
+        Ok(upgrades)
+    }
+}
+
+/// Represents a single Managed-Database Cluster
+#[derive(Debug, Deserialize)]
+pub struct Database {
+    /// The ID of the Database-Cluster
+    pub id: String,
+    /// The Name of the Database-Cluster
+    pub name: String,
+    /// The Slug of the Database-Engine, like "pg", "mysql" or "redis"
+    pub engine: String,
+    /// The Version of the Database-Engine
+    pub version: String,
+    /// The current Status of the Database-Cluster, like "creating" or "online"
+    pub status: String,
+    /// The Number of Nodes in the Database-Cluster
+    pub num_nodes: u64,
+    /// The Slug of the Size of the Nodes
+    pub size: String,
+    /// The Slug of the Region in which the Database-Cluster exists
+    pub region: String,
+    /// The Size of the Storage in MiB
+    #[serde(default)]
+    pub storage_size_mib: u64,
+    /// The Maintenance-Window of the Database-Cluster
+    #[serde(default)]
+    pub maintenance_window: Option<MaintenanceWindow>,
+    /// The Users of the Database-Cluster, this is not available for every Engine
+    #[serde(default)]
+    pub users: Option<Vec<DatabaseUser>>,
+    /// The Time at which this was created
+    pub created_at: String,
+}
+
+impl Database {
+    /// Whether or not the Engine of the Database-Cluster supports Read-only Replicas
+    pub fn supports_replicas(&self) -> bool {
+        matches!(self.engine.as_str(), "pg" | "mysql")
+    }
+
+    /// Whether or not the Engine of the Database-Cluster supports Connection-Pools
+    pub fn supports_connection_pools(&self) -> bool {
+        self.engine == "pg"
+    }
+}
+
+/// The Window in which Maintenance will be performed on a Database-Cluster
+#[derive(Debug, Deserialize)]
+pub struct MaintenanceWindow {
+    /// The Day of the Week, like "tuesday"
+    pub day: String,
+    /// The Time of Day, in the Format "HH:MM:SS"
+    pub hour: String,
+    /// Whether or not there is Maintenance pending
+    #[serde(default)]
+    pub pending: bool,
+}
+
+/// Represents a single User of a Database-Cluster
+#[derive(Debug, Deserialize)]
+pub struct DatabaseUser {
+    /// The Name of the User
+    pub name: String,
+    /// The Role of the User, like "primary" or "normal"
+    pub role: String,
+}
+
+/// Represents a List of Database-Clusters that can be loaded from the API
+pub struct Databases {}
+
+#[async_trait]
+impl APIRessource for Databases {
+    type LoadData = Vec<Database>;
+
+    async fn load(api: &API) -> Result<Self::LoadData, GetResouceError> {
+        let databases = api.get_list("/databases", "databases").await?;
+
+        Ok(databases)
+    }
+}
+
+/// Represents a single Read-only Replica of a Database-Cluster
+#[derive(Debug, Deserialize)]
+pub struct DatabaseReplica {
+    /// The Name of the Replica
+    pub name: String,
+    /// The Slug of the Region in which the Replica exists
+    pub region: String,
+    /// The current Status of the Replica
+    pub status: String,
+}
+
+/// Represents the List of Replicas for a single Database-Cluster
+pub struct DatabaseReplicas {}
+
+impl DatabaseReplicas {
+    /// Loads the Replicas of the Database-Cluster with the given ID
+    pub async fn load_for(
+        api: &API,
+        database_id: &str,
+    ) -> Result<Vec<DatabaseReplica>, GetResouceError> {
+        let raw_body = api
+            .get(format!("/databases/{}/replicas", database_id))
+            .await?;
+
+        let raw_replicas = raw_body
+            .get("replicas")
+            .ok_or(GetResouceError::MissingData)?;
+
+        let replicas = serde_json::from_value(raw_replicas.clone())?;
+
+        Ok(replicas)
+    }
+}
+
+/// Represents a single Connection-Pool of a Database-Cluster
+#[derive(Debug, Deserialize)]
+pub struct DatabasePool {
+    /// The Name of the Connection-Pool
+    pub name: String,
+    /// The Mode of the Connection-Pool, like "transaction" or "session"
+    pub mode: String,
+    /// The Number of Connections in the Connection-Pool
+    pub size: u64,
+    /// The Database the Connection-Pool connects to
+    pub db: String,
+}
+
+/// Represents the List of Connection-Pools for a single Database-Cluster
+pub struct DatabasePools {}
+
+impl DatabasePools {
+    /// Loads the Connection-Pools of the Database-Cluster with the given ID
+    pub async fn load_for(
+        api: &API,
+        database_id: &str,
+    ) -> Result<Vec<DatabasePool>, GetResouceError> {
+        let raw_body = api.get(format!("/databases/{}/pools", database_id)).await?;
+
+        let raw_pools = raw_body.get("pools").ok_or(GetResouceError::MissingData)?;
+
+        let pools = serde_json::from_value(raw_pools.clone())?;
+
+        Ok(pools)
+    }
+}
+
+/// Represents a single Load-Balancer
+#[derive(Debug, Deserialize)]
+pub struct LoadBalancer {
+    /// The ID of the Load-Balancer
+    pub id: String,
+    /// The Name of the Load-Balancer
+    pub name: String,
+    /// The public IP of the Load-Balancer
+    #[serde(default)]
+    pub ip: String,
+    /// The Slug of the Size of the Load-Balancer, empty if the Size is specified using the
+    /// `size_unit`
+    #[serde(default)]
+    pub size: String,
+    /// The Number of Nodes of the Load-Balancer
+    #[serde(default)]
+    pub size_unit: Option<u64>,
+    /// The Algorithm used to distribute the Traffic, like "round_robin" or "least_connections"
+    pub algorithm: String,
+    /// The current Status of the Load-Balancer, like "new", "active" or "errored"
+    pub status: String,
+    /// The Rules that define how Traffic is forwarded to the Droplets
+    pub forwarding_rules: Vec<ForwardingRule>,
+    /// The IDs of the Droplets to which the Traffic is forwarded
+    #[serde(default)]
+    pub droplet_ids: Vec<u64>,
+    /// The Region in which the Load-Balancer exists
+    pub region: Region,
+    /// The Time at which this was created
+    pub created_at: String,
+}
+
+/// A single Rule that defines how Traffic is forwarded by a Load-Balancer
+#[derive(Debug, Deserialize)]
+pub struct ForwardingRule {
+    /// The Protocol of the incoming Traffic
+    pub entry_protocol: String,
+    /// The Port on which the Load-Balancer receives the Traffic
+    pub entry_port: u64,
+    /// The Protocol used to forward the Traffic to the Droplets
+    pub target_protocol: String,
+    /// The Port on the Droplets to which the Traffic is forwarded
+    pub target_port: u64,
+    /// The ID of the Certificate used to terminate TLS
+    #[serde(default)]
+    pub certificate_id: String,
+}
+
+/// Represents a List of Load-Balancers that can be loaded from the API
+pub struct LoadBalancers {}
+
+#[async_trait]
+impl APIRessource for LoadBalancers {
+    type LoadData = Vec<LoadBalancer>;
+
+    async fn load(api: &API) -> Result<Self::LoadData, GetResouceError> {
+        let load_balancers = api.get_list("/load_balancers", "load_balancers").await?;
+
+        Ok(load_balancers)
+    }
+}
+
+/// Represents a single Domain managed by DigitalOcean's DNS
+#[derive(Debug, Deserialize)]
+pub struct Domain {
+    /// The Name of the Domain
+    pub name: String,
+    /// The TTL of the Zone in seconds
+    #[serde(default)]
+    pub ttl: Option<u64>,
+}
+
+/// Represents a List of Domains that can be loaded from the API
+pub struct Domains {}
+
+#[async_trait]
+impl APIRessource for Domains {
+    type LoadData = Vec<Domain>;
+
+    async fn load(api: &API) -> Result<Self::LoadData, GetResouceError> {
+        let domains = api.get_list("/domains", "domains").await?;
+
+        Ok(domains)
+    }
+}
 
-        let raw_endpoints = raw_body
-            .get("endpoints")
+/// Represents a single DNS-Record of a Domain
+#[derive(Debug, Deserialize)]
+pub struct DomainRecord {
+    /// The ID of the Record
+    pub id: u64,
+    /// The Type of the Record, like "A", "CNAME" or "TXT"
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The Host-Name of the Record, relative to the Domain
+    pub name: String,
+    /// The Value of the Record
+    pub data: String,
+    /// The TTL of the Record in seconds
+    pub ttl: u64,
+}
+
+/// Represents the List of DNS-Records for a single Domain
+pub struct DomainRecords {}
+
+impl DomainRecords {
+    /// Loads all the DNS-Records of the given Domain
+    pub async fn load_for(api: &API, domain: &str) -> Result<Vec<DomainRecord>, GetResouceError> {
+        let records = api
+            .get_list(&format!("/domains/{}/records", domain), "domain_records")
+            .await?;
+
+        Ok(records)
+    }
+}
+
+/// Represents a single TLS-Certificate
+#[derive(Debug, Deserialize)]
+pub struct Certificate {
+    /// The ID of the Certificate
+    pub id: String,
+    /// The Name of the Certificate
+    pub name: String,
+    /// The Time at which the Certificate expires, as an RFC-3339 Timestamp
+    pub not_after: String,
+    /// The SHA-1 Fingerprint of the Certificate
+    pub sha1_fingerprint: String,
+    /// The DNS-Names for which the Certificate is valid
+    #[serde(default)]
+    pub dns_names: Vec<String>,
+    /// The current State of the Certificate, like "pending", "verified" or "error"
+    pub state: String,
+    /// The Type of the Certificate, like "custom" or "lets_encrypt"
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The Time at which this was created
+    pub created_at: String,
+}
+
+/// Represents a List of Certificates that can be loaded from the API
+pub struct Certificates {}
+
+#[async_trait]
+impl APIRessource for Certificates {
+    type LoadData = Vec<Certificate>;
+
+    async fn load(api: &API) -> Result<Self::LoadData, GetResouceError> {
+        let certificates = api.get_list("/certificates", "certificates").await?;
+
+        Ok(certificates)
+    }
+}
+
+/// Represents a single Cloud-Firewall
+#[derive(Debug, Deserialize)]
+pub struct Firewall {
+    /// The ID of the Firewall
+    pub id: String,
+    /// The Name of the Firewall
+    pub name: String,
+    /// The current Status of the Firewall, like "waiting", "succeeded" or "failed"
+    pub status: String,
+    /// The Rules for the incoming Traffic
+    #[serde(default)]
+    pub inbound_rules: Vec<serde_json::Value>,
+    /// The Rules for the outgoing Traffic
+    #[serde(default)]
+    pub outbound_rules: Vec<serde_json::Value>,
+    /// The IDs of the Droplets to which the Firewall is applied
+    #[serde(default)]
+    pub droplet_ids: Vec<u64>,
+    /// The Tags of the Droplets to which the Firewall is applied
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The Time at which this was created
+    pub created_at: String,
+}
+
+impl Firewall {
+    /// Whether or not the Firewall is applied to the given Droplet, either directly or through
+    /// one of its Tags
+    pub fn applies_to(&self, droplet: &Droplet) -> bool {
+        self.droplet_ids.contains(&droplet.id) || droplet.tags.iter().any(|t| self.tags.contains(t))
+    }
+}
+
+/// Represents a List of Firewalls that can be loaded from the API
+pub struct Firewalls {}
+
+#[async_trait]
+impl APIRessource for Firewalls {
+    type LoadData = Vec<Firewall>;
+
+    async fn load(api: &API) -> Result<Self::LoadData, GetResouceError> {
+        let firewalls = api.get_list("/firewalls", "firewalls").await?;
+
+        Ok(firewalls)
+    }
+}
+
+/// A single Time-Series returned by the Monitoring-API
+#[derive(Debug, Deserialize)]
+pub struct MonitoringSeries {
+    /// The Labels identifying this Series, like the "mode" for CPU-Metrics
+    #[serde(default)]
+    pub metric: HashMap<String, String>,
+    /// The Samples of this Series, as Pairs of Unix-Timestamp and Value
+    #[serde(default)]
+    pub values: Vec<(f64, String)>,
+}
+
+impl MonitoringSeries {
+    /// Returns the Value of the latest Sample in this Series
+    pub fn latest(&self) -> Option<f64> {
+        self.values
+            .iter()
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .and_then(|(_, value)| value.parse().ok())
+    }
+
+    /// Returns the Value of the given Label or an empty String if the Label is not set
+    pub fn label(&self, name: &str) -> &str {
+        self.metric
+            .get(name)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+}
+
+/// Represents the Metrics collected by the Monitoring-Agent on a Droplet
+pub struct DropletMonitoring {}
+
+impl DropletMonitoring {
+    /// Loads the given Metric (like "cpu" or "memory_total") for the Droplet with the given ID
+    /// in the Time-Range from `start` to `end` (as Unix-Timestamps).
+    ///
+    /// Some Metrics require additional Parameters, like the "interface" and "direction" for the
+    /// "bandwidth" Metric, which can be passed using `params`.
+    pub async fn load_for(
+        api: &API,
+        metric: &str,
+        host_id: u64,
+        start: i64,
+        end: i64,
+        params: &[(&str, &str)],
+    ) -> Result<Vec<MonitoringSeries>, GetResouceError> {
+        let mut resource = format!(
+            "/monitoring/metrics/droplet/{}?host_id={}&start={}&end={}",
+            metric, host_id, start, end
+        );
+        for (key, value) in params {
+            resource.push_str(&format!("&{}={}", key, value));
+        }
+
+        let raw_body = api.get(resource).await?;
+
+        let raw_series = raw_body
+            .pointer("/data/result")
             .ok_or(GetResouceError::MissingData)?;
 
-        let endpoints = serde_json::from_value(raw_endpoints.clone())?;
+        let series = serde_json::from_value(raw_series.clone())?;
 
-        Ok(endpoints)
+        Ok(series)
     }
 }
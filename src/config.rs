@@ -0,0 +1,833 @@
+//! The Configuration of the Exporter, which can be loaded from a TOML or YAML File and
+//! overwritten using Environment-Variables
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use serde::Deserialize;
+
+use crate::{api, Collector};
+
+/// The Error returned when the Configuration could not be loaded
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The Configuration-File could not be read
+    Read(PathBuf, std::io::Error),
+    /// The Configuration-File has an unknown Extension, only TOML and YAML are supported
+    UnknownFormat(PathBuf),
+    /// The TOML Configuration-File could not be parsed
+    Toml(toml::de::Error),
+    /// The YAML Configuration-File could not be parsed
+    Yaml(serde_yaml::Error),
+    /// The Configuration was loaded, but contains invalid Values
+    Invalid(Vec<String>),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(path, e) => write!(f, "Reading {:?}: {}", path, e),
+            Self::UnknownFormat(path) => write!(
+                f,
+                "Unknown Format of {:?}, expected a .toml, .yaml or .yml File",
+                path
+            ),
+            Self::Toml(e) => write!(f, "Parsing TOML: {}", e),
+            Self::Yaml(e) => write!(f, "Parsing YAML: {}", e),
+            Self::Invalid(errors) => {
+                writeln!(f, "The Configuration is invalid:")?;
+                for error in errors {
+                    writeln!(f, "  - {}", error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// The entire Configuration of the Exporter
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The Address on which the Metrics will be served
+    pub listen_address: SocketAddr,
+    /// The Path to the YAML-File configuring TLS and Authentication of the Webserver, in the
+    /// Format of Prometheus' `web-config.yml`
+    pub web_config_file: Option<PathBuf>,
+    /// The LogLevel to use (debug, info, warn, error)
+    pub log_level: String,
+    /// The Format in which the Logs are written
+    pub log_format: LogFormat,
+    /// The DigitalOcean API-Token to use, if only a single Account should be monitored
+    pub token: Option<String>,
+    /// The Path to a File containing the DigitalOcean API-Token, this takes precedence over
+    /// the `token`
+    pub token_file: Option<PathBuf>,
+    /// The Accounts (or Teams) that should be monitored, every Collector is run for every
+    /// Account. Can not be combined with the top-level `token` or `token_file`, which are
+    /// turned into a single Account named "default" when loading the Configuration
+    pub accounts: Vec<AccountConfig>,
+    /// The Time between two Updates of the Metrics, used by all Collectors without their own
+    /// Interval. If this is not set, every Collector uses its own default Interval
+    #[serde(with = "humantime_serde")]
+    pub update_interval: Option<Duration>,
+    /// The maximum Number of Collectors that are run at the same Time for a single Account
+    pub concurrency: usize,
+    /// The Time after which a single Run of a Collector is aborted and counted as failed
+    #[serde(with = "humantime_serde")]
+    pub collector_timeout: Duration,
+    /// When the Metrics are loaded from the API
+    pub mode: Mode,
+    /// The minimum Time for which the Metrics are reused between Scrapes, when loading them on
+    /// Scrape
+    #[serde(with = "humantime_serde")]
+    pub cache_ttl: Duration,
+    /// The Configuration for the individual Collectors, by their Name
+    pub collectors: BTreeMap<String, CollectorConfig>,
+    /// The Configuration of the API-Client
+    pub api: ApiConfig,
+    /// The Configuration of the exposed Metrics
+    pub labels: LabelConfig,
+}
+
+/// The Format in which the Logs are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Every Log-Entry is a single JSON-Object
+    Json,
+    /// Human readable Log-Entries
+    Pretty,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "pretty" => Ok(Self::Pretty),
+            other => Err(format!(
+                "Unknown Log-Format {:?}, expected json or pretty",
+                other
+            )),
+        }
+    }
+}
+
+/// When the Metrics are loaded from the API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    /// Every Collector is run periodically in its Interval
+    Interval,
+    /// All Collectors are run when the Metrics are scraped, unless they have been loaded within
+    /// the `cache_ttl`
+    Scrape,
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "interval" => Ok(Self::Interval),
+            "scrape" => Ok(Self::Scrape),
+            other => Err(format!(
+                "Unknown Mode {:?}, expected interval or scrape",
+                other
+            )),
+        }
+    }
+}
+
+/// Values that take precedence over both the Configuration-File and the Environment-Variables,
+/// like the Command-Line Arguments
+#[derive(Debug, Default)]
+pub struct Overrides {
+    /// Overrides the `listen_address`
+    pub listen_address: Option<SocketAddr>,
+    /// Overrides the `web_config_file`
+    pub web_config_file: Option<PathBuf>,
+    /// Overrides the `update_interval`
+    pub update_interval: Option<Duration>,
+    /// Overrides the `log_format`
+    pub log_format: Option<LogFormat>,
+    /// Overrides the `token_file`
+    pub token_file: Option<PathBuf>,
+}
+
+/// The Configuration of a single DigitalOcean Account
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountConfig {
+    /// The Name of the Account, which is used as the `account` Label of all its Metrics
+    pub name: String,
+    /// The DigitalOcean API-Token of the Account
+    #[serde(default)]
+    pub token: Option<String>,
+    /// The Path to a File containing the DigitalOcean API-Token of the Account, this takes
+    /// precedence over the `token`
+    #[serde(default)]
+    pub token_file: Option<PathBuf>,
+}
+
+/// The Configuration of a single Collector
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CollectorConfig {
+    /// Whether or not the Collector should be run, defaults to whether the Collector is enabled
+    /// by default
+    pub enabled: Option<bool>,
+    /// The Time between two Runs of the Collector, defaults to the global `update_interval` or
+    /// the default Interval of the Collector
+    #[serde(with = "humantime_serde")]
+    pub interval: Option<Duration>,
+}
+
+/// The Configuration of the API-Client
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiConfig {
+    /// The Base-URL against which all the Requests are made
+    pub base_url: Option<String>,
+    /// The User-Agent send with every Request
+    pub user_agent: Option<String>,
+    /// The Timeout for establishing a Connection, defaults to 10 Seconds
+    #[serde(with = "humantime_serde")]
+    pub connect_timeout: Option<Duration>,
+    /// The Timeout for an entire Request, defaults to 30 Seconds
+    #[serde(with = "humantime_serde")]
+    pub timeout: Option<Duration>,
+    /// The URL of the HTTP(S)-Proxy to use for all Requests
+    pub proxy: Option<String>,
+    /// The Path to a File containing additional trusted Certificates
+    pub ca_bundle: Option<PathBuf>,
+}
+
+/// The Configuration of the exposed Metrics
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LabelConfig {
+    /// The Prefix for the Names of all the Metrics
+    pub namespace: String,
+    /// Labels that will be added to every Metric
+    pub constant: BTreeMap<String, String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            listen_address: SocketAddr::from(([0, 0, 0, 0], 9100)),
+            web_config_file: None,
+            log_level: "info".to_string(),
+            log_format: LogFormat::Json,
+            token: None,
+            token_file: None,
+            accounts: Vec::new(),
+            update_interval: None,
+            concurrency: crate::exporter::DEFAULT_CONCURRENCY,
+            collector_timeout: crate::exporter::DEFAULT_TIMEOUT,
+            mode: Mode::Interval,
+            cache_ttl: Duration::from_secs(10),
+            collectors: BTreeMap::new(),
+            api: ApiConfig::default(),
+            labels: LabelConfig::default(),
+        }
+    }
+}
+
+impl Default for LabelConfig {
+    fn default() -> Self {
+        Self {
+            namespace: "digitalocean".to_string(),
+            constant: BTreeMap::new(),
+        }
+    }
+}
+
+/// Checks whether the given Name is a valid Prometheus Metric- or Label-Name
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    };
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl Config {
+    /// Loads the Configuration from the given File, if any, applies the Overrides from the
+    /// Environment-Variables and then the given Overrides and validates the Result.
+    ///
+    /// All the invalid Values are reported at once, instead of stopping at the first one.
+    pub fn load(path: Option<&Path>, overrides: Overrides) -> Result<Self, ConfigError> {
+        let mut config = match path {
+            Some(path) => Self::from_file(path)?,
+            None => Self::default(),
+        };
+
+        let mut errors = config.apply_env();
+        config.apply_overrides(overrides);
+        errors.extend(config.normalize_accounts());
+        errors.extend(config.load_token_files());
+        errors.extend(config.validate());
+
+        if !errors.is_empty() {
+            return Err(ConfigError::Invalid(errors));
+        }
+        Ok(config)
+    }
+
+    /// Parses the Configuration from the given File, the Format is determined by the Extension
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| ConfigError::Read(path.to_owned(), e))?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml::from_str(&content).map_err(ConfigError::Toml),
+            Some("yaml") | Some("yml") => serde_yaml::from_str(&content).map_err(ConfigError::Yaml),
+            _ => Err(ConfigError::UnknownFormat(path.to_owned())),
+        }
+    }
+
+    /// Overwrites the Values with the ones from the Environment-Variables, returning a List
+    /// of all the Variables that could not be parsed
+    fn apply_env(&mut self) -> Vec<String> {
+        let var = |name: &str| std::env::var(name).ok();
+        let mut errors = Vec::new();
+
+        if let Some(level) = var("LOG") {
+            self.log_level = level;
+        }
+        if let Some(token) = var("DIGITALOCEAN_TOKEN") {
+            self.token = Some(token);
+        }
+        if let Some(path) = var("DIGITALOCEAN_TOKEN_FILE") {
+            self.token_file = Some(PathBuf::from(path));
+        }
+        if let Some(address) = var("RUSTYOCEAN_LISTEN_ADDRESS") {
+            match address.parse() {
+                Ok(a) => self.listen_address = a,
+                Err(e) => errors.push(format!("RUSTYOCEAN_LISTEN_ADDRESS {:?}: {}", address, e)),
+            }
+        }
+        if let Some(path) = var("RUSTYOCEAN_WEB_CONFIG_FILE") {
+            self.web_config_file = Some(PathBuf::from(path));
+        }
+        if let Some(interval) = var("RUSTYOCEAN_UPDATE_INTERVAL") {
+            match humantime::parse_duration(&interval) {
+                Ok(i) => self.update_interval = Some(i),
+                Err(e) => errors.push(format!("RUSTYOCEAN_UPDATE_INTERVAL {:?}: {}", interval, e)),
+            }
+        }
+        if let Some(mode) = var("RUSTYOCEAN_MODE") {
+            match mode.parse() {
+                Ok(m) => self.mode = m,
+                Err(e) => errors.push(format!("RUSTYOCEAN_MODE: {}", e)),
+            }
+        }
+        if let Some(collectors) = var("RUSTYOCEAN_COLLECTORS") {
+            let enabled: Vec<&str> = collectors
+                .split(',')
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .collect();
+
+            for collector in Collector::ALL.iter() {
+                self.collectors
+                    .entry(collector.name().to_string())
+                    .or_default()
+                    .enabled = Some(enabled.contains(&collector.name()));
+            }
+            for name in enabled {
+                if Collector::from_name(name).is_none() {
+                    errors.push(format!(
+                        "RUSTYOCEAN_COLLECTORS: Unknown Collector {:?}",
+                        name
+                    ));
+                }
+            }
+        }
+
+        errors
+    }
+
+    /// Overwrites the Values with all the given Overrides that are set
+    fn apply_overrides(&mut self, overrides: Overrides) {
+        if let Some(address) = overrides.listen_address {
+            self.listen_address = address;
+        }
+        if let Some(path) = overrides.web_config_file {
+            self.web_config_file = Some(path);
+        }
+        if let Some(interval) = overrides.update_interval {
+            self.update_interval = Some(interval);
+        }
+        if let Some(format) = overrides.log_format {
+            self.log_format = format;
+        }
+        if let Some(path) = overrides.token_file {
+            self.token_file = Some(path);
+        }
+    }
+
+    /// Turns the top-level `token` and `token_file` into a single Account, if no Accounts
+    /// have been configured explicitly
+    fn normalize_accounts(&mut self) -> Option<String> {
+        if !self.accounts.is_empty() {
+            if self.token.is_some() || self.token_file.is_some() {
+                return Some(
+                    "accounts: Can not be combined with the top-level token or token_file"
+                        .to_string(),
+                );
+            }
+            return None;
+        }
+
+        if self.token.is_some() || self.token_file.is_some() {
+            self.accounts.push(AccountConfig {
+                name: api::DEFAULT_NAME.to_string(),
+                token: self.token.take(),
+                token_file: self.token_file.take(),
+            });
+        }
+        None
+    }
+
+    /// Reads the Tokens from the `token_file` of every Account, if one is configured
+    fn load_token_files(&mut self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        for account in self.accounts.iter_mut() {
+            let path = match account.token_file.as_ref() {
+                Some(p) => p,
+                None => continue,
+            };
+
+            match std::fs::read_to_string(path) {
+                Ok(token) => account.token = Some(token.trim().to_string()),
+                Err(e) => errors.push(format!(
+                    "accounts.{}.token_file: Reading {:?}: {}",
+                    account.name, path, e
+                )),
+            }
+        }
+
+        errors
+    }
+
+    /// Checks the Configuration for invalid Values, returning a Description of every Problem
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if !matches!(
+            self.log_level.as_str(),
+            "trace" | "debug" | "info" | "warn" | "error"
+        ) {
+            errors.push(format!("log_level: Unknown Level {:?}", self.log_level));
+        }
+        if let Some(path) = self.web_config_file.as_ref() {
+            if !path.is_file() {
+                errors.push(format!("web_config_file: {:?} is not a File", path));
+            }
+        }
+        if self.accounts.is_empty() {
+            errors.push("token: No API-Token configured".to_string());
+        }
+        for (index, account) in self.accounts.iter().enumerate() {
+            if account.name.trim().is_empty() {
+                errors.push(format!("accounts.{}.name: The Name is empty", index));
+            } else if self.accounts[..index]
+                .iter()
+                .any(|other| other.name == account.name)
+            {
+                errors.push(format!(
+                    "accounts.{}.name: Duplicate Name {:?}",
+                    index, account.name
+                ));
+            }
+
+            match account.token.as_deref() {
+                // A missing token_file has already been reported while loading it
+                None if account.token_file.is_some() => {}
+                None => errors.push(format!(
+                    "accounts.{}.token: No API-Token configured",
+                    account.name
+                )),
+                Some(t) if t.trim().is_empty() => errors.push(format!(
+                    "accounts.{}.token: The API-Token is empty",
+                    account.name
+                )),
+                Some(_) => {}
+            };
+        }
+        if self.update_interval.map(|i| i.is_zero()).unwrap_or(false) {
+            errors.push("update_interval: Must be greater than 0".to_string());
+        }
+        if self.concurrency == 0 {
+            errors.push("concurrency: Must be greater than 0".to_string());
+        }
+        if self.collector_timeout.is_zero() {
+            errors.push("collector_timeout: Must be greater than 0".to_string());
+        }
+        if self.cache_ttl.is_zero() {
+            errors.push("cache_ttl: Must be greater than 0".to_string());
+        }
+
+        for (name, collector) in self.collectors.iter() {
+            if Collector::from_name(name).is_none() {
+                errors.push(format!("collectors.{}: Unknown Collector", name));
+            }
+            if collector.interval.map(|i| i.is_zero()).unwrap_or(false) {
+                errors.push(format!(
+                    "collectors.{}.interval: Must be greater than 0",
+                    name
+                ));
+            }
+        }
+
+        if let Some(url) = self.api.base_url.as_ref() {
+            if reqwest::Url::parse(url).is_err() {
+                errors.push(format!("api.base_url: Invalid URL {:?}", url));
+            }
+        }
+        if self
+            .api
+            .connect_timeout
+            .map(|t| t.is_zero())
+            .unwrap_or(false)
+        {
+            errors.push("api.connect_timeout: Must be greater than 0".to_string());
+        }
+        if self.api.timeout.map(|t| t.is_zero()).unwrap_or(false) {
+            errors.push("api.timeout: Must be greater than 0".to_string());
+        }
+        if let Some(proxy) = self.api.proxy.as_ref() {
+            if reqwest::Proxy::all(proxy).is_err() {
+                errors.push(format!("api.proxy: Invalid URL {:?}", proxy));
+            }
+        }
+        if let Some(path) = self.api.ca_bundle.as_ref() {
+            if !path.is_file() {
+                errors.push(format!("api.ca_bundle: {:?} is not a File", path));
+            }
+        }
+
+        if !is_valid_name(&self.labels.namespace) {
+            errors.push(format!(
+                "labels.namespace: Invalid Name {:?}",
+                self.labels.namespace
+            ));
+        }
+        let used = crate::metrics::label_names();
+        for name in self.labels.constant.keys() {
+            if !is_valid_name(name) || name.starts_with("__") {
+                errors.push(format!("labels.constant: Invalid Label-Name {:?}", name));
+            } else if used.contains(name) {
+                // The Registry adds constant Labels without checking for Duplicates
+                errors.push(format!(
+                    "labels.constant: {:?} is already used by the Metrics",
+                    name
+                ));
+            }
+        }
+
+        errors
+    }
+
+    /// All the enabled Collectors together with the Interval in which they should be run
+    pub fn collectors(&self) -> Vec<(Collector, Duration)> {
+        Collector::ALL
+            .iter()
+            .filter_map(|collector| match self.collectors.get(collector.name()) {
+                Some(c) if !c.enabled.unwrap_or_else(|| collector.enabled_by_default()) => None,
+                Some(c) => Some((
+                    *collector,
+                    c.interval.unwrap_or_else(|| self.interval(collector)),
+                )),
+                None if !collector.enabled_by_default() => None,
+                None => Some((*collector, self.interval(collector))),
+            })
+            .collect()
+    }
+
+    /// The Interval for the given Collector, if it has no Interval of its own
+    fn interval(&self, collector: &Collector) -> Duration {
+        self.update_interval
+            .unwrap_or_else(|| collector.default_interval())
+    }
+
+    /// Creates the API-Client for the given Account according to this Configuration
+    pub fn build_api(&self, account: &AccountConfig) -> Result<api::API, api::BuildError> {
+        let mut builder =
+            api::API::builder(account.token.clone().unwrap_or_default()).name(account.name.clone());
+        if let Some(url) = self.api.base_url.as_ref() {
+            builder = builder.base_url(url);
+        }
+        if let Some(agent) = self.api.user_agent.as_ref() {
+            builder = builder.user_agent(agent);
+        }
+        if let Some(timeout) = self.api.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.api.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(proxy) = self.api.proxy.as_ref() {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(path) = self.api.ca_bundle.as_ref() {
+            builder = builder.ca_bundle(path);
+        }
+
+        builder.build()
+    }
+
+    /// Creates the Registry for the Metrics, using the configured Namespace and Labels
+    pub fn registry(&self) -> prometheus::Registry {
+        let labels = self
+            .labels
+            .constant
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        prometheus::Registry::new_custom(Some(self.labels.namespace.clone()), Some(labels))
+            .expect("The Labels have already been validated")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses the TOML-Configuration and returns all the Errors found while validating it
+    fn errors(toml: &str) -> Vec<String> {
+        let mut config: Config = toml::from_str(toml).unwrap();
+        let mut errors: Vec<_> = config.normalize_accounts().into_iter().collect();
+        errors.extend(config.validate());
+        errors
+    }
+
+    #[test]
+    fn accepts_minimal_config() {
+        assert_eq!(errors(r#"token = "secret""#), Vec::<String>::new());
+    }
+
+    #[test]
+    fn requires_token() {
+        assert_eq!(errors(""), vec!["token: No API-Token configured"]);
+    }
+
+    #[test]
+    fn reports_all_errors() {
+        let errors = errors(
+            r#"
+            token = "secret"
+            log_level = "verbose"
+            update_interval = "0s"
+            concurrency = 0
+            collector_timeout = "0s"
+            cache_ttl = "0s"
+
+            [collectors.unknown]
+            enabled = false
+
+            [collectors.droplets]
+            interval = "0s"
+
+            [api]
+            base_url = "not a url"
+            timeout = "0s"
+
+            [labels]
+            namespace = "digital-ocean"
+            constant = { "__reserved" = "x", "0invalid" = "y", valid = "z" }
+            "#,
+        );
+
+        assert_eq!(
+            errors,
+            vec![
+                r#"log_level: Unknown Level "verbose""#,
+                "update_interval: Must be greater than 0",
+                "concurrency: Must be greater than 0",
+                "collector_timeout: Must be greater than 0",
+                "cache_ttl: Must be greater than 0",
+                "collectors.droplets.interval: Must be greater than 0",
+                "collectors.unknown: Unknown Collector",
+                r#"api.base_url: Invalid URL "not a url""#,
+                "api.timeout: Must be greater than 0",
+                r#"labels.namespace: Invalid Name "digital-ocean""#,
+                r#"labels.constant: Invalid Label-Name "0invalid""#,
+                r#"labels.constant: Invalid Label-Name "__reserved""#,
+            ]
+        );
+    }
+
+    #[test]
+    fn validates_accounts() {
+        let errors = errors(
+            r#"
+            [[accounts]]
+            name = "production"
+            token = "secret"
+
+            [[accounts]]
+            name = "production"
+            token = "other"
+
+            [[accounts]]
+            name = " "
+            token = "secret"
+
+            [[accounts]]
+            name = "staging"
+            token = ""
+
+            [[accounts]]
+            name = "development"
+            "#,
+        );
+
+        assert_eq!(
+            errors,
+            vec![
+                r#"accounts.1.name: Duplicate Name "production""#,
+                "accounts.2.name: The Name is empty",
+                "accounts.staging.token: The API-Token is empty",
+                "accounts.development.token: No API-Token configured",
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_token_with_accounts() {
+        let errors = errors(
+            r#"
+            token = "secret"
+
+            [[accounts]]
+            name = "production"
+            token = "secret"
+            "#,
+        );
+
+        assert_eq!(
+            errors,
+            vec!["accounts: Can not be combined with the top-level token or token_file"]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        assert!(toml::from_str::<Config>("unknown = 1").is_err());
+        assert!(toml::from_str::<Config>("[api]\ntimeot = \"1s\"").is_err());
+    }
+
+    #[test]
+    fn parses_yaml() {
+        let config: Config = serde_yaml::from_str(
+            "token: secret\nupdate_interval: 2m\ncollectors:\n  balance:\n    enabled: false\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.update_interval, Some(Duration::from_secs(120)));
+        assert_eq!(config.collectors["balance"].enabled, Some(false));
+    }
+
+    #[test]
+    fn resolves_collector_intervals() {
+        let config: Config = toml::from_str(
+            r#"
+            [collectors.balance]
+            enabled = false
+
+            [collectors.droplets]
+            interval = "10s"
+            "#,
+        )
+        .unwrap();
+        let collectors = config.collectors();
+
+        assert!(!collectors.iter().any(|(c, _)| *c == Collector::Balance));
+        assert!(collectors.contains(&(Collector::Droplets, Duration::from_secs(10))));
+        assert!(collectors.contains(&(Collector::Account, Collector::Account.default_interval())));
+        assert!(!collectors.iter().any(|(c, _)| *c == Collector::Monitoring));
+
+        let config: Config = toml::from_str(
+            r#"
+            update_interval = "5m"
+
+            [collectors.droplets]
+            interval = "10s"
+            "#,
+        )
+        .unwrap();
+        let collectors = config.collectors();
+
+        assert!(collectors.contains(&(Collector::Droplets, Duration::from_secs(10))));
+        assert!(collectors.contains(&(Collector::Account, Duration::from_secs(300))));
+    }
+
+    #[test]
+    fn enables_monitoring_explicitly() {
+        let config: Config = toml::from_str(
+            r#"
+            [collectors.monitoring]
+            enabled = true
+            "#,
+        )
+        .unwrap();
+
+        assert!(config
+            .collectors()
+            .contains(&(Collector::Monitoring, Duration::from_secs(15 * 60))));
+    }
+
+    #[test]
+    fn rejects_used_label_names() {
+        let errors = errors(
+            r#"
+            token = "secret"
+
+            [labels]
+            constant = { account = "a", region = "b", le = "c", environment = "d" }
+            "#,
+        );
+
+        assert_eq!(
+            errors,
+            vec![
+                r#"labels.constant: "account" is already used by the Metrics"#,
+                r#"labels.constant: "le" is already used by the Metrics"#,
+                r#"labels.constant: "region" is already used by the Metrics"#,
+            ]
+        );
+
+        let used = crate::metrics::label_names();
+        for name in ["account", "id", "name", "region", "collector", "endpoint"] {
+            assert!(used.contains(name), "{} is not used", name);
+        }
+    }
+
+    #[test]
+    fn validates_names() {
+        assert!(is_valid_name("digitalocean"));
+        assert!(is_valid_name("_private"));
+        assert!(is_valid_name("label_1"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("1label"));
+        assert!(!is_valid_name("with-dash"));
+        assert!(!is_valid_name("with:colon"));
+    }
+}
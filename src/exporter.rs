@@ -0,0 +1,240 @@
+//! The Exporter, which ties together the API-Client of a single Account with all of its Metrics
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures_util::future;
+use prometheus::{core, Registry};
+use tokio::sync::Semaphore;
+
+use crate::{
+    api::{self, GetResouceError},
+    metrics, sd, Collector,
+};
+
+/// The Number of Collectors that are run at the same Time, if not configured otherwise
+pub(crate) const DEFAULT_CONCURRENCY: usize = 4;
+
+/// The Time after which a single Run of a Collector is aborted, if not configured otherwise
+pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The Outcome of the last Runs of a single Collector
+#[derive(Debug, Clone)]
+pub struct CollectorStatus {
+    /// When the last Run finished
+    pub last_run: chrono::DateTime<chrono::Utc>,
+    /// How long the last Run took
+    pub duration: Duration,
+    /// The Error of the last Run, if it failed
+    pub error: Option<String>,
+    /// When the last successful Run finished, if any Run has been successful so far
+    pub last_success: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Loads the Metrics of a single Account from the DigitalOcean API.
+///
+/// All the Metrics are owned by the Exporter itself and carry the Name of its Account as the
+/// constant `account` Label, so the Exporters of multiple Accounts can be registered in the same
+/// Registry.
+pub struct Exporter {
+    /// The API-Client used to load all the Data
+    client: Arc<api::API>,
+    /// The enabled Collectors together with the Interval in which they should be run
+    collectors: Vec<(Collector, Duration)>,
+    /// The Service-Discovery that is updated with the loaded Droplets
+    discovery: Arc<sd::Discovery>,
+    /// Limits the Number of Collectors that are run at the same Time
+    permits: Semaphore,
+    /// The Time after which a single Run of a Collector is aborted
+    timeout: Duration,
+    /// The Outcome of the last Run of every Collector that has been run so far
+    status: Mutex<HashMap<Collector, CollectorStatus>>,
+    /// The Metrics about the Collectors themselves
+    collector_metrics: metrics::collector::Metrics,
+    /// The Metrics of all the Collectors
+    metrics: metrics::Metrics,
+}
+
+impl Exporter {
+    /// Creates a new Exporter for the Account of the given API-Client, that runs the given
+    /// Collectors in their Interval
+    pub fn new(client: Arc<api::API>, collectors: Vec<(Collector, Duration)>) -> Self {
+        let account = client.name().to_string();
+
+        Self {
+            client,
+            collectors,
+            discovery: Arc::new(sd::Discovery::new()),
+            permits: Semaphore::new(DEFAULT_CONCURRENCY),
+            timeout: DEFAULT_TIMEOUT,
+            status: Mutex::new(HashMap::new()),
+            collector_metrics: metrics::collector::Metrics::new(&account),
+            metrics: metrics::Metrics::new(&account),
+        }
+    }
+
+    /// Uses the given Service-Discovery instead of a separate one, which allows for sharing a
+    /// single Discovery between the Exporters of multiple Accounts
+    pub fn with_discovery(mut self, discovery: Arc<sd::Discovery>) -> Self {
+        self.discovery = discovery;
+        self
+    }
+
+    /// Sets the maximum Number of Collectors that are run at the same Time, which is at least 1
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.permits = Semaphore::new(concurrency.max(1));
+        self
+    }
+
+    /// Sets the Time after which a single Run of a Collector is aborted and counted as failed,
+    /// so a single hanging Request can't hold up the other Collectors
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The API-Client used by this Exporter
+    pub fn client(&self) -> &Arc<api::API> {
+        &self.client
+    }
+
+    /// The Service-Discovery that is updated with the Droplets loaded by this Exporter
+    pub fn discovery(&self) -> &Arc<sd::Discovery> {
+        &self.discovery
+    }
+
+    /// The enabled Collectors of this Exporter
+    pub fn collectors(&self) -> impl Iterator<Item = Collector> + '_ {
+        self.collectors.iter().map(|(collector, _)| *collector)
+    }
+
+    /// The enabled Collectors of this Exporter together with the Interval in which they should
+    /// be run
+    pub fn schedule(&self) -> &[(Collector, Duration)] {
+        &self.collectors
+    }
+
+    /// Registers the Metrics of all the enabled Collectors, as well as the Metrics about the
+    /// Exporter itself, in the given Registry
+    pub fn register(&self, registry: &Registry) -> prometheus::Result<()> {
+        for metric in self.metric_collectors() {
+            registry.register(metric)?;
+        }
+        Ok(())
+    }
+
+    /// The Metrics of all the enabled Collectors, as well as the Metrics about the Exporter
+    /// itself
+    pub(crate) fn metric_collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        let mut metrics = self.collector_metrics.collectors();
+        metrics.extend(self.client.metrics().collectors());
+        for collector in self.collectors() {
+            metrics.extend(self.metrics.collectors(collector));
+        }
+        metrics
+    }
+
+    /// Runs the given Collector once to update its Metrics, without recording the Run itself
+    pub async fn update(&self, collector: Collector) -> Result<(), GetResouceError> {
+        self.metrics
+            .update(collector, &self.client, &self.discovery)
+            .await
+    }
+
+    /// Sends the single cheap Request of the given Collector, to check whether the Token has the
+    /// Permissions required by it, without updating any Metrics
+    pub async fn probe(&self, collector: Collector) -> Result<(), api::GetError> {
+        self.client.get(collector.probe()).await.map(|_| ())
+    }
+
+    /// Runs the given Collector once and records whether it succeeded and how long it took,
+    /// returning whether it was successful.
+    ///
+    /// This waits until fewer than the configured Number of Collectors are running, before
+    /// starting the Collector.
+    #[tracing::instrument(skip(self))]
+    pub async fn run(&self, collector: Collector) -> bool {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .expect("The Semaphore is never closed");
+
+        let start = Instant::now();
+        let result = tokio::time::timeout(self.timeout, self.update(collector)).await;
+        let duration = start.elapsed();
+
+        let error = match result {
+            Ok(Ok(_)) => None,
+            Ok(Err(e)) => Some(format!("{:?}", e)),
+            Err(_) => Some(format!("Timed out after {:?}", self.timeout)),
+        };
+        if let Some(e) = error.as_ref() {
+            tracing::error!(
+                "Running Collector {:?} for Account {:?}: {}",
+                collector.name(),
+                self.client.name(),
+                e
+            );
+        }
+        let success = error.is_none();
+
+        self.collector_metrics.observe(collector, duration, success);
+        self.record_status(collector, duration, error);
+        success
+    }
+
+    /// Stores the Outcome of a Run of the given Collector
+    fn record_status(&self, collector: Collector, duration: Duration, error: Option<String>) {
+        let now = chrono::Utc::now();
+
+        let mut status = self.status.lock().unwrap();
+        let last_success = match error {
+            None => Some(now),
+            Some(_) => status.get(&collector).and_then(|s| s.last_success),
+        };
+        status.insert(
+            collector,
+            CollectorStatus {
+                last_run: now,
+                duration,
+                error,
+                last_success,
+            },
+        );
+    }
+
+    /// The Outcome of the last Run of every enabled Collector, which is None for Collectors that
+    /// have not been run yet
+    pub fn status(&self) -> Vec<(Collector, Option<CollectorStatus>)> {
+        let status = self.status.lock().unwrap();
+        self.collectors()
+            .map(|collector| (collector, status.get(&collector).cloned()))
+            .collect()
+    }
+
+    /// Whether every enabled Collector has been run successfully at least once, so all of the
+    /// Metrics have been loaded
+    pub fn is_ready(&self) -> bool {
+        let status = self.status.lock().unwrap();
+        self.collectors().all(|collector| {
+            status
+                .get(&collector)
+                .map(|s| s.last_success.is_some())
+                .unwrap_or(false)
+        })
+    }
+
+    /// Runs all the enabled Collectors once to update their Metrics, returning whether all of
+    /// them were successful
+    pub async fn load_metrics(&self) -> bool {
+        let runs = self.collectors().map(|collector| self.run(collector));
+        future::join_all(runs)
+            .await
+            .into_iter()
+            .all(|success| success)
+    }
+}
@@ -3,39 +3,51 @@
 //! Prometheus for easier monitoring of your Infrastructure
 
 pub mod api;
+pub mod config;
+pub mod scrape;
+pub mod sd;
+pub mod server;
 
-use std::{sync::Arc, time::Duration};
-
+mod exporter;
 mod metrics;
 
-/// This will register all the needed Metrics
-pub fn register_metrics(registry: &prometheus::Registry) {
-    metrics::account::register_metrics(registry);
-    metrics::balance::register_metrics(registry);
-    metrics::droplets::register_metrics(registry);
-    metrics::floating_ip::register_metrics(registry);
-    metrics::vpc::register_metrics(registry);
-    metrics::cdn_endpoint::register_metrics(registry);
-}
+use std::time::Duration;
+
+use futures_util::future;
+use tokio::time::MissedTickBehavior;
+
+pub use exporter::{CollectorStatus, Exporter};
+pub use metrics::Collector;
 
-#[tracing::instrument(skip(client))]
-async fn load_metrics(client: &api::API) {
-    metrics::account::update(client).await;
-    metrics::balance::update(client).await;
-    metrics::droplets::update(client).await;
-    metrics::floating_ip::update(client).await;
-    metrics::vpc::update(client).await;
-    metrics::cdn_endpoint::update(client).await;
+/// This will run forever and periodically load the new Data from the API to keep the Metrics of
+/// the given Exporter up to date.
+///
+/// Every Collector runs on its own Schedule, so a slow Collector only delays its own next Run
+/// and not the ones of the other Collectors. The Concurrency-Limit of the Exporter still applies
+/// to all of them together.
+pub async fn update_metrics(exporter: &Exporter) {
+    if exporter.schedule().is_empty() {
+        tracing::warn!("No Collectors are enabled");
+        return;
+    }
+
+    let runs = exporter
+        .schedule()
+        .iter()
+        .map(|(collector, interval)| run_every(exporter, *collector, *interval));
+    future::join_all(runs).await;
 }
 
-/// This will run forever in an infinite loop and periodically load the new Data from the API to
-/// keep the Metrics up to date
-#[tracing::instrument(skip(client))]
-pub async fn update_metrics(client: Arc<api::API>, wait_time: Duration) {
-    loop {
-        tracing::debug!("Updating-Metrics...");
-        load_metrics(&client).await;
+/// Runs the given Collector forever, starting right away and then once every Interval
+#[tracing::instrument(skip(exporter))]
+async fn run_every(exporter: &Exporter, collector: Collector, interval: Duration) {
+    let mut ticks = tokio::time::interval(interval);
+    // A Run that takes longer than the Interval delays the next one, instead of causing a Burst
+    // of Runs to catch up
+    ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
-        tokio::time::sleep(wait_time).await;
+    loop {
+        ticks.tick().await;
+        exporter.run(collector).await;
     }
 }
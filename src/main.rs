@@ -1,61 +1,275 @@
-use std::{sync::Arc, time::Duration};
+use std::{io::Write, net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
 
+use clap::{Parser, Subcommand};
 use prometheus::Encoder;
-use rustyocean::{api, register_metrics, update_metrics};
+use rustyocean::{
+    api,
+    config::{AccountConfig, Config, LogFormat, Mode, Overrides},
+    scrape::ScrapeCollector,
+    sd, server, update_metrics, Collector, Exporter,
+};
 
-use lazy_static::lazy_static;
+/// A Prometheus-Exporter for DigitalOcean
+#[derive(Debug, Parser)]
+#[clap(version, about)]
+struct Cli {
+    /// The Path to the Configuration-File (TOML or YAML)
+    #[clap(long, global = true, env = "RUSTYOCEAN_CONFIG")]
+    config: Option<PathBuf>,
+    /// The Address on which the Metrics will be served
+    #[clap(long, global = true)]
+    listen_address: Option<SocketAddr>,
+    /// The Path to the YAML-File configuring TLS and Authentication of the Webserver
+    #[clap(long, global = true)]
+    web_config_file: Option<PathBuf>,
+    /// The Time between two Updates of all the Metrics, like "60s" or "5m", overriding the
+    /// default Interval of every Collector
+    #[clap(long, global = true, value_parser = humantime::parse_duration)]
+    interval: Option<Duration>,
+    /// The Format of the Logs (json, pretty)
+    #[clap(long, global = true)]
+    log_format: Option<LogFormat>,
+    /// The Path to a File containing the DigitalOcean API-Token
+    #[clap(long, global = true)]
+    token_file: Option<PathBuf>,
 
-lazy_static! {
-    static ref REGISTRY: prometheus::Registry =
-        prometheus::Registry::new_custom(Some("digitalocean".to_owned()), None).unwrap();
+    #[clap(subcommand)]
+    command: Option<Command>,
 }
 
-async fn handle(_req: hyper::Request<hyper::Body>) -> Result<hyper::Response<hyper::Body>, String> {
-    let mut buffer = Vec::new();
-    let encoder = prometheus::TextEncoder::new();
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Serves the Metrics and periodically updates them (default)
+    Serve,
+    /// Runs every enabled Collector once and prints the Metrics to stdout
+    Once,
+    /// Validates the Token and its Permissions with one small Request per enabled Collector
+    Check,
+}
+
+fn setup_tracing(config: &Config, command: &Command) {
+    let tracing_directive_str = format!("rustyocean={}", config.log_level);
+    let filter = tracing_subscriber::EnvFilter::from_default_env()
+        .add_directive(tracing_directive_str.parse().unwrap());
 
-    let metrics = REGISTRY.gather();
+    // Only the Metrics should be written to stdout when running once
+    let to_stderr = !matches!(command, Command::Serve);
 
-    encoder.encode(&metrics, &mut buffer).unwrap();
-    Ok(hyper::Response::new(hyper::Body::from(buffer)))
+    let builder = tracing_subscriber::FmtSubscriber::builder()
+        .with_level(true)
+        .with_env_filter(filter);
+    let result = match (config.log_format, to_stderr) {
+        (LogFormat::Json, false) => {
+            tracing::subscriber::set_global_default(builder.json().finish())
+        }
+        (LogFormat::Json, true) => tracing::subscriber::set_global_default(
+            builder.json().with_writer(std::io::stderr).finish(),
+        ),
+        (LogFormat::Pretty, false) => tracing::subscriber::set_global_default(builder.finish()),
+        (LogFormat::Pretty, true) => {
+            tracing::subscriber::set_global_default(builder.with_writer(std::io::stderr).finish())
+        }
+    };
+    result.expect("Setting initial Tracing-Subscriber");
 }
 
-async fn run_server(port: u16) {
-    let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
+/// The Interval in which the Token-File is checked for a new Token
+const TOKEN_RELOAD_INTERVAL: Duration = Duration::from_secs(10);
 
-    let make_service = hyper::service::make_service_fn(|_conn| async {
-        Ok::<_, String>(hyper::service::service_fn(handle))
-    });
+/// The Interval in which the TLS-Certificate and -Key are checked for Changes
+const TLS_RELOAD_INTERVAL: Duration = Duration::from_secs(30);
 
-    let server = hyper::Server::bind(&addr).serve(make_service);
+/// The Exporter of a single Account together with the Configuration of that Account
+type AccountExporter = (Arc<Exporter>, AccountConfig);
 
-    if let Err(e) = server.await {
-        tracing::error!("Running Webserver: {:?}", e);
+/// Registers the Metrics of all the Exporters in a new Registry
+fn registry(config: &Config, exporters: &[AccountExporter]) -> prometheus::Registry {
+    let registry = config.registry();
+    for (exporter, account) in exporters.iter() {
+        if let Err(e) = exporter.register(&registry) {
+            tracing::error!(
+                "Registering Metrics for Account {:?}: {:?}",
+                account.name,
+                e
+            );
+            std::process::exit(1);
+        }
     }
+    registry
+}
+
+fn serve(
+    rt: &tokio::runtime::Runtime,
+    config: &Config,
+    discovery: Arc<sd::Discovery>,
+    exporters: Vec<AccountExporter>,
+) -> bool {
+    let mut scrapers = Vec::new();
+    let registry = match config.mode {
+        Mode::Interval => registry(config, &exporters),
+        Mode::Scrape => {
+            let registry = config.registry();
+            for (exporter, account) in exporters.iter() {
+                let scraper = ScrapeCollector::new(exporter.clone(), config.cache_ttl);
+                if let Err(e) = registry.register(Box::new(scraper.clone())) {
+                    tracing::error!(
+                        "Registering Metrics for Account {:?}: {:?}",
+                        account.name,
+                        e
+                    );
+                    std::process::exit(1);
+                }
+                scrapers.push(scraper);
+            }
+            registry
+        }
+    };
+
+    let web_config = match config.web_config_file.as_ref() {
+        Some(path) => match server::WebConfig::from_file(path) {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::error!("Loading Web-Configuration: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => server::WebConfig::default(),
+    };
+    let tls = match web_config.tls_server_config.clone() {
+        Some(files) => match server::ReloadingConfig::load(files) {
+            Ok(c) => Some(Arc::new(c)),
+            Err(e) => {
+                tracing::error!("Loading TLS-Configuration: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+    if let Some(tls) = tls.clone() {
+        rt.spawn(tls.watch(TLS_RELOAD_INTERVAL));
+    }
+
+    let state = server::State {
+        registry,
+        discovery,
+        exporters: exporters.iter().map(|(e, _)| e.clone()).collect(),
+        scrapers,
+        auth: server::Auth::new(&web_config),
+    };
+
+    for (exporter, account) in exporters {
+        if let Some(path) = account.token_file {
+            rt.spawn(api::watch_key_file(
+                exporter.client().clone(),
+                path,
+                TOKEN_RELOAD_INTERVAL,
+            ));
+        }
+
+        if config.mode == Mode::Interval {
+            rt.spawn(async move { update_metrics(&exporter).await });
+        }
+    }
+
+    match rt.block_on(server::run(config.listen_address, state, tls)) {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::error!("{}", e);
+            false
+        }
+    }
+}
+
+fn once(rt: &tokio::runtime::Runtime, config: &Config, exporters: Vec<AccountExporter>) -> bool {
+    let registry = registry(config, &exporters);
+
+    let mut success = true;
+    for (exporter, _) in exporters.iter() {
+        success &= rt.block_on(exporter.load_metrics());
+    }
+
+    let mut buffer = Vec::new();
+    prometheus::TextEncoder::new()
+        .encode(&registry.gather(), &mut buffer)
+        .unwrap();
+    std::io::stdout().write_all(&buffer).unwrap();
+
+    success
+}
+
+fn check(rt: &tokio::runtime::Runtime, exporters: Vec<AccountExporter>) -> bool {
+    let mut success = true;
+    for (exporter, account) in exporters.iter() {
+        // The Account is always checked first, as it works with every valid Token
+        let collectors = std::iter::once(Collector::Account)
+            .chain(exporter.collectors().filter(|c| *c != Collector::Account));
+
+        for collector in collectors {
+            match rt.block_on(exporter.probe(collector)) {
+                Ok(_) => println!("ok      {}/{}", account.name, collector.name()),
+                Err(e) => {
+                    println!("failed  {}/{}: {:?}", account.name, collector.name(), e);
+                    success = false;
+
+                    // An invalid Token fails every other Request as well
+                    let unauthorized = matches!(
+                        e,
+                        api::GetError::StatusCode(reqwest::StatusCode::UNAUTHORIZED)
+                    );
+                    if collector == Collector::Account && unauthorized {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    success
 }
 
 fn main() {
-    let listen_port: u16 = 9100;
-    let update_wait_time = Duration::from_secs(60);
-
-    // Setting up the logging/tracing stuff
-    let log_level = std::env::var("LOG").unwrap_or_else(|_| "info".to_string());
-    let tracing_directive_str = format!("rustyocean={}", log_level);
-    let tracing_sub = tracing_subscriber::FmtSubscriber::builder()
-        .json()
-        .with_level(true)
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing_directive_str.parse().unwrap()),
-        )
-        .finish();
-    tracing::subscriber::set_global_default(tracing_sub)
-        .expect("Setting initial Tracing-Subscriber");
+    let cli = Cli::parse();
+    let command = cli.command.unwrap_or(Command::Serve);
+
+    let overrides = Overrides {
+        listen_address: cli.listen_address,
+        web_config_file: cli.web_config_file,
+        update_interval: cli.interval,
+        log_format: cli.log_format,
+        token_file: cli.token_file,
+    };
+    let config = match Config::load(cli.config.as_deref(), overrides) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    setup_tracing(&config, &command);
 
     tracing::info!("Starting...");
 
-    let token = std::env::var("DIGITALOCEAN_TOKEN").unwrap();
-    let client = Arc::new(api::API::new(token));
+    let discovery = Arc::new(sd::Discovery::new());
+    let mut exporters = Vec::with_capacity(config.accounts.len());
+    for account in config.accounts.iter() {
+        match config.build_api(account) {
+            Ok(c) => {
+                let exporter = Exporter::new(Arc::new(c), config.collectors())
+                    .with_discovery(discovery.clone())
+                    .with_concurrency(config.concurrency)
+                    .with_timeout(config.collector_timeout);
+                exporters.push((Arc::new(exporter), account.clone()));
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Creating API-Client for Account {:?}: {:?}",
+                    account.name,
+                    e
+                );
+                std::process::exit(1);
+            }
+        };
+    }
 
     let rt = tokio::runtime::Builder::new_multi_thread()
         .enable_io()
@@ -63,8 +277,12 @@ fn main() {
         .build()
         .unwrap();
 
-    register_metrics(&REGISTRY);
-
-    rt.spawn(update_metrics(client, update_wait_time));
-    rt.block_on(run_server(listen_port));
+    let success = match command {
+        Command::Serve => serve(&rt, &config, discovery, exporters),
+        Command::Once => once(&rt, &config, exporters),
+        Command::Check => check(&rt, exporters),
+    };
+    if !success {
+        std::process::exit(1);
+    }
 }
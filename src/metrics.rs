@@ -1,8 +1,267 @@
 //! Contains all the Metrics that will be exposed by this Exporter for DigitalOcean
+//!
+//! Collectors that need additional Requests for every Resource, like the Records of a Domain,
+//! load everything before clearing and filling their Metrics. A Scrape in between therefore
+//! always sees the complete previous or new Data, instead of Resources with only part of their
+//! Metrics.
+
+use std::{collections::BTreeSet, time::Duration};
+
+use prometheus::{core, Opts};
+
+use crate::{
+    api::{self, GetResouceError},
+    sd,
+};
 
 pub mod account;
 pub mod balance;
 pub mod cdn_endpoint;
+pub mod certificates;
+pub mod collector;
+pub mod databases;
+pub mod domains;
 pub mod droplets;
+pub mod firewalls;
 pub mod floating_ip;
+pub mod kubernetes;
+pub mod load_balancer;
+pub mod monitoring;
+pub mod requests;
+pub mod volumes;
 pub mod vpc;
+
+/// Creates the Options for a Metric of the given Account, which is attached as a constant
+/// `account` Label, so the Metrics of multiple Accounts can share a single Registry
+fn opts(account: &str, name: &str, help: &str) -> Opts {
+    Opts::new(name, help).const_label("account", account)
+}
+
+/// The Names of all the Labels that are attached to the Metrics of the Collectors and of the
+/// Exporter itself, including the `le` Label of the Histogram-Buckets
+pub(crate) fn label_names() -> BTreeSet<String> {
+    let metrics = Metrics::new("");
+    let mut collectors = collector::Metrics::new("").collectors();
+    collectors.extend(requests::Metrics::new("").collectors());
+    for collector in Collector::ALL.iter() {
+        collectors.extend(metrics.collectors(*collector));
+    }
+
+    let mut names: BTreeSet<_> = collectors
+        .iter()
+        .flat_map(|c| c.desc())
+        .flat_map(|desc| {
+            let constant = desc
+                .const_label_pairs
+                .iter()
+                .map(|l| l.get_name().to_string());
+            desc.variable_labels.iter().cloned().chain(constant)
+        })
+        .collect();
+    names.insert("le".to_string());
+    names
+}
+
+/// A single Collector, which is responsible for loading one Part of the Metrics from the API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Collector {
+    /// The Limits of the Account
+    Account,
+    /// The Balance and Usage of the Account
+    Balance,
+    /// The Droplets and their Sizing
+    Droplets,
+    /// The Floating-IPs
+    FloatingIps,
+    /// The VPCs
+    Vpcs,
+    /// The CDN-Endpoints
+    CdnEndpoints,
+    /// The Block-Storage Volumes
+    Volumes,
+    /// The Kubernetes-Clusters and their Node-Pools
+    Kubernetes,
+    /// The Managed-Database Clusters
+    Databases,
+    /// The Load-Balancers
+    LoadBalancers,
+    /// The Domains and their DNS-Records
+    Domains,
+    /// The TLS-Certificates
+    Certificates,
+    /// The Cloud-Firewalls
+    Firewalls,
+    /// The Utilisation of the Droplets from the Monitoring-API
+    Monitoring,
+}
+
+impl Collector {
+    /// All the available Collectors
+    pub const ALL: [Collector; 14] = [
+        Collector::Account,
+        Collector::Balance,
+        Collector::Droplets,
+        Collector::FloatingIps,
+        Collector::Vpcs,
+        Collector::CdnEndpoints,
+        Collector::Volumes,
+        Collector::Kubernetes,
+        Collector::Databases,
+        Collector::LoadBalancers,
+        Collector::Domains,
+        Collector::Certificates,
+        Collector::Firewalls,
+        Collector::Monitoring,
+    ];
+
+    /// The Name of the Collector, as used in Labels
+    pub fn name(&self) -> &'static str {
+        match self {
+            Collector::Account => "account",
+            Collector::Balance => "balance",
+            Collector::Droplets => "droplets",
+            Collector::FloatingIps => "floating_ips",
+            Collector::Vpcs => "vpcs",
+            Collector::CdnEndpoints => "cdn_endpoints",
+            Collector::Volumes => "volumes",
+            Collector::Kubernetes => "kubernetes",
+            Collector::Databases => "databases",
+            Collector::LoadBalancers => "load_balancers",
+            Collector::Domains => "domains",
+            Collector::Certificates => "certificates",
+            Collector::Firewalls => "firewalls",
+            Collector::Monitoring => "monitoring",
+        }
+    }
+
+    /// The Interval in which the Collector is run, if none is configured. Data that rarely
+    /// changes, like the Balance, or that needs many Requests, like the Monitoring, is loaded
+    /// less often to save on API-Requests
+    pub fn default_interval(&self) -> Duration {
+        match self {
+            Collector::Account
+            | Collector::Balance
+            | Collector::Certificates
+            | Collector::Monitoring => Duration::from_secs(15 * 60),
+            Collector::Droplets => Duration::from_secs(30),
+            _ => Duration::from_secs(60),
+        }
+    }
+
+    /// Whether the Collector is run, unless it is enabled or disabled explicitly. The Monitoring
+    /// needs 9 Requests for every Droplet and therefore has to be enabled explicitly
+    pub fn enabled_by_default(&self) -> bool {
+        !matches!(self, Collector::Monitoring)
+    }
+
+    /// A single cheap Request that needs the same Permissions as the Collector, which is used
+    /// to check the Token without running the entire Collector
+    pub fn probe(&self) -> &'static str {
+        match self {
+            Collector::Account => "/account",
+            Collector::Balance => "/customers/my/balance",
+            Collector::Droplets => "/droplets?per_page=1",
+            Collector::FloatingIps => "/floating_ips?per_page=1",
+            Collector::Vpcs => "/vpcs?per_page=1",
+            Collector::CdnEndpoints => "/cdn/endpoints?per_page=1",
+            Collector::Volumes => "/volumes?per_page=1",
+            Collector::Kubernetes => "/kubernetes/clusters?per_page=1",
+            Collector::Databases => "/databases?per_page=1",
+            Collector::LoadBalancers => "/load_balancers?per_page=1",
+            Collector::Domains => "/domains?per_page=1",
+            Collector::Certificates => "/certificates?per_page=1",
+            Collector::Firewalls => "/firewalls?per_page=1",
+            Collector::Monitoring => "/monitoring/alerts?per_page=1",
+        }
+    }
+
+    /// Looks up the Collector with the given Name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|c| c.name() == name)
+    }
+}
+
+/// The Metrics of all the Collectors for a single Account
+pub struct Metrics {
+    account: account::Metrics,
+    balance: balance::Metrics,
+    droplets: droplets::Metrics,
+    floating_ips: floating_ip::Metrics,
+    vpcs: vpc::Metrics,
+    cdn_endpoints: cdn_endpoint::Metrics,
+    volumes: volumes::Metrics,
+    kubernetes: kubernetes::Metrics,
+    databases: databases::Metrics,
+    load_balancers: load_balancer::Metrics,
+    domains: domains::Metrics,
+    certificates: certificates::Metrics,
+    firewalls: firewalls::Metrics,
+    monitoring: monitoring::Metrics,
+}
+
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            account: account::Metrics::new(account),
+            balance: balance::Metrics::new(account),
+            droplets: droplets::Metrics::new(account),
+            floating_ips: floating_ip::Metrics::new(account),
+            vpcs: vpc::Metrics::new(account),
+            cdn_endpoints: cdn_endpoint::Metrics::new(account),
+            volumes: volumes::Metrics::new(account),
+            kubernetes: kubernetes::Metrics::new(account),
+            databases: databases::Metrics::new(account),
+            load_balancers: load_balancer::Metrics::new(account),
+            domains: domains::Metrics::new(account),
+            certificates: certificates::Metrics::new(account),
+            firewalls: firewalls::Metrics::new(account),
+            monitoring: monitoring::Metrics::new(account),
+        }
+    }
+
+    /// All the Metrics of the given Collector, which can be registered in a Registry
+    pub fn collectors(&self, collector: Collector) -> Vec<Box<dyn core::Collector>> {
+        match collector {
+            Collector::Account => self.account.collectors(),
+            Collector::Balance => self.balance.collectors(),
+            Collector::Droplets => self.droplets.collectors(),
+            Collector::FloatingIps => self.floating_ips.collectors(),
+            Collector::Vpcs => self.vpcs.collectors(),
+            Collector::CdnEndpoints => self.cdn_endpoints.collectors(),
+            Collector::Volumes => self.volumes.collectors(),
+            Collector::Kubernetes => self.kubernetes.collectors(),
+            Collector::Databases => self.databases.collectors(),
+            Collector::LoadBalancers => self.load_balancers.collectors(),
+            Collector::Domains => self.domains.collectors(),
+            Collector::Certificates => self.certificates.collectors(),
+            Collector::Firewalls => self.firewalls.collectors(),
+            Collector::Monitoring => self.monitoring.collectors(),
+        }
+    }
+
+    /// Loads the Data for the given Collector from the API and updates its Metrics
+    pub async fn update(
+        &self,
+        collector: Collector,
+        client: &api::API,
+        discovery: &sd::Discovery,
+    ) -> Result<(), GetResouceError> {
+        match collector {
+            Collector::Account => self.account.update(client).await,
+            Collector::Balance => self.balance.update(client).await,
+            Collector::Droplets => self.droplets.update(client, discovery).await,
+            Collector::FloatingIps => self.floating_ips.update(client).await,
+            Collector::Vpcs => self.vpcs.update(client).await,
+            Collector::CdnEndpoints => self.cdn_endpoints.update(client).await,
+            Collector::Volumes => self.volumes.update(client).await,
+            Collector::Kubernetes => self.kubernetes.update(client).await,
+            Collector::Databases => self.databases.update(client).await,
+            Collector::LoadBalancers => self.load_balancers.update(client).await,
+            Collector::Domains => self.domains.update(client).await,
+            Collector::Certificates => self.certificates.update(client).await,
+            Collector::Firewalls => self.firewalls.update(client, discovery).await,
+            Collector::Monitoring => self.monitoring.update(client).await,
+        }
+    }
+}
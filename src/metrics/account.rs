@@ -1,45 +1,56 @@
-use lazy_static::lazy_static;
-use prometheus::{IntGauge, Registry};
+use prometheus::{core, IntGauge};
 
-use crate::api::{self, Account};
+use crate::api::{self, Account, GetResouceError};
 
-lazy_static! {
-    static ref DROPLET_LIMIT: IntGauge = IntGauge::new(
-        "droplet_limit",
-        "The Number of Droplets your Account is allowed to have"
-    )
-    .unwrap();
-    static ref FLOATING_IP_LIMIT: IntGauge = IntGauge::new(
-        "floating_ip_limit",
-        "The Number of Floating-IPs your Account is allowed to have"
-    )
-    .unwrap();
-    static ref VOLUME_LIMIT: IntGauge = IntGauge::new(
-        "volume_limit",
-        "The Number of Volumes your Account is allowed to have"
-    )
-    .unwrap();
+/// The Metrics for the Limits of a single Account
+pub struct Metrics {
+    droplet_limit: IntGauge,
+    floating_ip_limit: IntGauge,
+    volume_limit: IntGauge,
 }
 
-pub fn register_metrics(registry: &Registry) {
-    registry.register(Box::new(DROPLET_LIMIT.clone())).unwrap();
-    registry
-        .register(Box::new(FLOATING_IP_LIMIT.clone()))
-        .unwrap();
-    registry.register(Box::new(VOLUME_LIMIT.clone())).unwrap();
-}
-
-#[tracing::instrument(skip(client))]
-pub async fn update(client: &api::API) {
-    let account = match client.load_resource::<Account>().await {
-        Ok(a) => a,
-        Err(e) => {
-            tracing::error!("Loading-Account: {:?}", e);
-            return;
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            droplet_limit: IntGauge::with_opts(super::opts(
+                account,
+                "droplet_limit",
+                "The Number of Droplets your Account is allowed to have",
+            ))
+            .unwrap(),
+            floating_ip_limit: IntGauge::with_opts(super::opts(
+                account,
+                "floating_ip_limit",
+                "The Number of Floating-IPs your Account is allowed to have",
+            ))
+            .unwrap(),
+            volume_limit: IntGauge::with_opts(super::opts(
+                account,
+                "volume_limit",
+                "The Number of Volumes your Account is allowed to have",
+            ))
+            .unwrap(),
         }
-    };
+    }
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![
+            Box::new(self.droplet_limit.clone()),
+            Box::new(self.floating_ip_limit.clone()),
+            Box::new(self.volume_limit.clone()),
+        ]
+    }
+
+    #[tracing::instrument(skip(self, client))]
+    pub async fn update(&self, client: &api::API) -> Result<(), GetResouceError> {
+        let account = client.load_resource::<Account>().await?;
+
+        self.droplet_limit.set(account.droplet_limit as i64);
+        self.floating_ip_limit.set(account.floating_ip_limit as i64);
+        self.volume_limit.set(account.volume_limit as i64);
 
-    DROPLET_LIMIT.set(account.droplet_limit as i64);
-    FLOATING_IP_LIMIT.set(account.floating_ip_limit as i64);
-    VOLUME_LIMIT.set(account.volume_limit as i64);
+        Ok(())
+    }
 }
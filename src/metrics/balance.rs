@@ -1,46 +1,62 @@
-use lazy_static::lazy_static;
-use prometheus::{Gauge, Registry};
+use prometheus::{core, Gauge};
 
-use crate::api::{self, Balance};
+use crate::api::{self, Balance, GetResouceError};
 
-lazy_static! {
-    static ref ACCOUNT_BALANCE: Gauge =
-        Gauge::new("account_balance", "The current Account-Balance").unwrap();
-    static ref MONTH_TO_DATE_BALANCE: Gauge =
-        Gauge::new("month_to_date_balance", "The current Balance with the Usage of the Month already subtracted from the Account-Balance").unwrap();
-    static ref MONTH_TO_DATE_USAGE: Gauge =
-        Gauge::new("month_to_date_usage", "The current Usage for this Month").unwrap();
+/// The Metrics for the Balance and Usage of a single Account
+pub struct Metrics {
+    account_balance: Gauge,
+    month_to_date_balance: Gauge,
+    month_to_date_usage: Gauge,
 }
 
-pub fn register_metrics(registry: &Registry) {
-    registry
-        .register(Box::new(ACCOUNT_BALANCE.clone()))
-        .unwrap();
-    registry
-        .register(Box::new(MONTH_TO_DATE_BALANCE.clone()))
-        .unwrap();
-    registry
-        .register(Box::new(MONTH_TO_DATE_USAGE.clone()))
-        .unwrap();
-}
-
-#[tracing::instrument(skip(client))]
-pub async fn update(client: &api::API) {
-    let balance = match client.load_resource::<Balance>().await {
-        Ok(b) => b,
-        Err(e) => {
-            tracing::error!("Loading-Balance: {:?}", e);
-            return;
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            account_balance: Gauge::with_opts(super::opts(
+                account,
+                "account_balance",
+                "The current Account-Balance",
+            ))
+            .unwrap(),
+            month_to_date_balance: Gauge::with_opts(super::opts(
+                account,
+                "month_to_date_balance",
+                "The current Balance with the Usage of the Month already subtracted from the Account-Balance",
+            ))
+            .unwrap(),
+            month_to_date_usage: Gauge::with_opts(super::opts(
+                account,
+                "month_to_date_usage",
+                "The current Usage for this Month",
+            ))
+            .unwrap(),
         }
-    };
-
-    if let Ok(acc_balance) = balance.account_balance.parse() {
-        ACCOUNT_BALANCE.set(acc_balance);
     }
-    if let Ok(month_to_date_balance) = balance.month_to_date_balance.parse() {
-        MONTH_TO_DATE_BALANCE.set(month_to_date_balance);
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![
+            Box::new(self.account_balance.clone()),
+            Box::new(self.month_to_date_balance.clone()),
+            Box::new(self.month_to_date_usage.clone()),
+        ]
     }
-    if let Ok(month_to_date_usage) = balance.month_to_date_usage.parse() {
-        MONTH_TO_DATE_USAGE.set(month_to_date_usage);
+
+    #[tracing::instrument(skip(self, client))]
+    pub async fn update(&self, client: &api::API) -> Result<(), GetResouceError> {
+        let balance = client.load_resource::<Balance>().await?;
+
+        if let Ok(acc_balance) = balance.account_balance.parse() {
+            self.account_balance.set(acc_balance);
+        }
+        if let Ok(month_to_date_balance) = balance.month_to_date_balance.parse() {
+            self.month_to_date_balance.set(month_to_date_balance);
+        }
+        if let Ok(month_to_date_usage) = balance.month_to_date_usage.parse() {
+            self.month_to_date_usage.set(month_to_date_usage);
+        }
+
+        Ok(())
     }
 }
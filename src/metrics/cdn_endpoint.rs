@@ -1,43 +1,53 @@
-use lazy_static::lazy_static;
-use prometheus::{labels, IntGaugeVec, Opts, Registry};
+use prometheus::{core, labels, IntGaugeVec};
 
-use crate::api::{self, CdnEndpoints};
+use crate::api::{self, CdnEndpoints, GetResouceError};
 
-lazy_static! {
-    static ref CDN_ENDPOINT: IntGaugeVec = IntGaugeVec::new(
-        Opts::new("cdn_endpoint", "Information about a CDN-Endpoint"),
-        &["id", "origin", "endpoint", "ttl", "custom_domain"]
-    )
-    .unwrap();
+/// The Metrics for the CDN-Endpoints of a single Account
+pub struct Metrics {
+    cdn_endpoint: IntGaugeVec,
 }
 
-pub fn register_metrics(registry: &Registry) {
-    registry.register(Box::new(CDN_ENDPOINT.clone())).unwrap();
-}
-
-#[tracing::instrument(skip(client))]
-pub async fn update(client: &api::API) {
-    let cdn_endpoints = match client.load_resource::<CdnEndpoints>().await {
-        Ok(c) => c,
-        Err(e) => {
-            tracing::error!("Loading CDN-Endpoints: {:?}", e);
-            return;
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            cdn_endpoint: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "cdn_endpoint_info",
+                    "Information about a CDN-Endpoint",
+                ),
+                &["id", "origin", "endpoint", "ttl", "custom_domain"],
+            )
+            .unwrap(),
         }
-    };
+    }
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![Box::new(self.cdn_endpoint.clone())]
+    }
+
+    #[tracing::instrument(skip(self, client))]
+    pub async fn update(&self, client: &api::API) -> Result<(), GetResouceError> {
+        let cdn_endpoints = client.load_resource::<CdnEndpoints>().await?;
 
-    CDN_ENDPOINT.reset();
+        self.cdn_endpoint.reset();
 
-    for cdn_endpoint in cdn_endpoints.iter() {
-        let ttl_str = cdn_endpoint.ttl.to_string();
+        for cdn_endpoint in cdn_endpoints.iter() {
+            let ttl_str = cdn_endpoint.ttl.to_string();
 
-        let cdn_endpoint_labels = labels! {
-            "id" => cdn_endpoint.id.as_ref(),
-            "origin" => cdn_endpoint.origin.as_ref(),
-            "endpoint" => cdn_endpoint.endpoint.as_ref(),
-            "ttl" => ttl_str.as_ref(),
-            "custom_domain" => cdn_endpoint.custom_domain.as_ref(),
-        };
+            let cdn_endpoint_labels = labels! {
+                "id" => cdn_endpoint.id.as_ref(),
+                "origin" => cdn_endpoint.origin.as_ref(),
+                "endpoint" => cdn_endpoint.endpoint.as_ref(),
+                "ttl" => ttl_str.as_ref(),
+                "custom_domain" => cdn_endpoint.custom_domain.as_ref(),
+            };
+
+            self.cdn_endpoint.with(&cdn_endpoint_labels).set(1);
+        }
 
-        CDN_ENDPOINT.with(&cdn_endpoint_labels).set(1);
+        Ok(())
     }
 }
@@ -0,0 +1,88 @@
+use prometheus::{core, labels, IntGaugeVec};
+
+use crate::api::{self, Certificates, GetResouceError};
+
+/// The Metrics for the TLS-Certificates of a single Account
+pub struct Metrics {
+    certificate: IntGaugeVec,
+    certificate_not_after_seconds: IntGaugeVec,
+}
+
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            certificate: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "certificate_info",
+                    "Information about a Certificate",
+                ),
+                &["id", "name", "type", "state", "dns_names"],
+            )
+            .unwrap(),
+            certificate_not_after_seconds: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "certificate_not_after_seconds",
+                    "The Unix-Timestamp at which a given Certificate expires",
+                ),
+                &["id", "name"],
+            )
+            .unwrap(),
+        }
+    }
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![
+            Box::new(self.certificate.clone()),
+            Box::new(self.certificate_not_after_seconds.clone()),
+        ]
+    }
+
+    #[tracing::instrument(skip(self, client))]
+    pub async fn update(&self, client: &api::API) -> Result<(), GetResouceError> {
+        let certificates = client.load_resource::<Certificates>().await?;
+
+        self.certificate.reset();
+        self.certificate_not_after_seconds.reset();
+
+        for certificate in certificates.iter() {
+            let dns_names_str = certificate.dns_names.join(",");
+
+            let certificate_info_labels = labels! {
+                "id" => certificate.id.as_ref(),
+                "name" => certificate.name.as_ref(),
+                "type" => certificate.ty.as_ref(),
+                "state" => certificate.state.as_ref(),
+                "dns_names" => dns_names_str.as_ref(),
+            };
+
+            self.certificate.with(&certificate_info_labels).set(1);
+
+            match chrono::DateTime::parse_from_rfc3339(&certificate.not_after) {
+                Ok(not_after) => {
+                    let certificate_labels = labels! {
+                        "id" => certificate.id.as_ref(),
+                        "name" => certificate.name.as_ref(),
+                    };
+
+                    self.certificate_not_after_seconds
+                        .with(&certificate_labels)
+                        .set(not_after.timestamp());
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Parsing Expiry of Certificate {:?} ({:?}): {:?}",
+                        certificate.id,
+                        certificate.not_after,
+                        e
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use prometheus::{core, labels, GaugeVec, IntGaugeVec};
+
+use super::Collector;
+
+/// The Metrics about the Runs of the Collectors for a single Account
+pub struct Metrics {
+    collector_success: IntGaugeVec,
+    collector_duration_seconds: GaugeVec,
+    collector_last_success_timestamp_seconds: GaugeVec,
+}
+
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            collector_success: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "collector_success",
+                    "If the last Run of a given Collector was successful",
+                ),
+                &["collector"],
+            )
+            .unwrap(),
+            collector_duration_seconds: GaugeVec::new(
+                super::opts(
+                    account,
+                    "collector_duration_seconds",
+                    "The Duration of the last Run of a given Collector",
+                ),
+                &["collector"],
+            )
+            .unwrap(),
+            collector_last_success_timestamp_seconds: GaugeVec::new(
+                super::opts(
+                    account,
+                    "collector_last_success_timestamp_seconds",
+                    "The Unix-Timestamp of the last successful Run of a given Collector",
+                ),
+                &["collector"],
+            )
+            .unwrap(),
+        }
+    }
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![
+            Box::new(self.collector_success.clone()),
+            Box::new(self.collector_duration_seconds.clone()),
+            Box::new(self.collector_last_success_timestamp_seconds.clone()),
+        ]
+    }
+
+    /// Records the Outcome and Duration of a single Run of the given Collector
+    pub fn observe(&self, collector: Collector, duration: Duration, success: bool) {
+        let collector_labels = labels! {
+            "collector" => collector.name(),
+        };
+
+        self.collector_duration_seconds
+            .with(&collector_labels)
+            .set(duration.as_secs_f64());
+
+        if !success {
+            self.collector_success.with(&collector_labels).set(0);
+            return;
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        self.collector_success.with(&collector_labels).set(1);
+        self.collector_last_success_timestamp_seconds
+            .with(&collector_labels)
+            .set(now.as_secs_f64());
+    }
+}
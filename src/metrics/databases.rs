@@ -0,0 +1,234 @@
+use prometheus::{core, labels, IntGaugeVec};
+
+use crate::api::{self, DatabasePools, DatabaseReplicas, Databases, GetResouceError};
+
+/// The Metrics for the Managed-Database Clusters of a single Account
+pub struct Metrics {
+    database: IntGaugeVec,
+    database_up: IntGaugeVec,
+    database_nodes: IntGaugeVec,
+    database_storage_size_bytes: IntGaugeVec,
+    database_maintenance_window: IntGaugeVec,
+    database_maintenance_pending: IntGaugeVec,
+    database_replicas: IntGaugeVec,
+    database_connection_pools: IntGaugeVec,
+    database_users: IntGaugeVec,
+}
+
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            database: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "database_info",
+                    "Information about a Database-Cluster",
+                ),
+                &[
+                    "id", "name", "engine", "version", "region", "size", "status",
+                ],
+            )
+            .unwrap(),
+            database_up: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "database_up",
+                    "If a given Database-Cluster is currently online",
+                ),
+                &["id", "name", "engine"],
+            )
+            .unwrap(),
+            database_nodes: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "database_nodes",
+                    "The Number of Nodes in a given Database-Cluster",
+                ),
+                &["id", "name", "engine"],
+            )
+            .unwrap(),
+            database_storage_size_bytes: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "database_storage_size_bytes",
+                    "The Size of the Storage for a given Database-Cluster in Bytes",
+                ),
+                &["id", "name", "engine"],
+            )
+            .unwrap(),
+            database_maintenance_window: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "database_maintenance_window",
+                    "The Maintenance-Window of a given Database-Cluster",
+                ),
+                &["id", "name", "engine", "day", "hour"],
+            )
+            .unwrap(),
+            database_maintenance_pending: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "database_maintenance_pending",
+                    "If there is Maintenance pending for a given Database-Cluster",
+                ),
+                &["id", "name", "engine"],
+            )
+            .unwrap(),
+            database_replicas: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "database_replicas",
+                    "The Number of Read-only Replicas of a given Database-Cluster",
+                ),
+                &["id", "name", "engine"],
+            )
+            .unwrap(),
+            database_connection_pools: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "database_connection_pools",
+                    "The Number of Connection-Pools of a given Database-Cluster",
+                ),
+                &["id", "name", "engine"],
+            )
+            .unwrap(),
+            database_users: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "database_users",
+                    "The Number of Users of a given Database-Cluster",
+                ),
+                &["id", "name", "engine"],
+            )
+            .unwrap(),
+        }
+    }
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![
+            Box::new(self.database.clone()),
+            Box::new(self.database_up.clone()),
+            Box::new(self.database_nodes.clone()),
+            Box::new(self.database_storage_size_bytes.clone()),
+            Box::new(self.database_maintenance_window.clone()),
+            Box::new(self.database_maintenance_pending.clone()),
+            Box::new(self.database_replicas.clone()),
+            Box::new(self.database_connection_pools.clone()),
+            Box::new(self.database_users.clone()),
+        ]
+    }
+
+    fn clear_metrics(&self) {
+        self.database.reset();
+        self.database_up.reset();
+        self.database_nodes.reset();
+        self.database_storage_size_bytes.reset();
+        self.database_maintenance_window.reset();
+        self.database_maintenance_pending.reset();
+        self.database_replicas.reset();
+        self.database_connection_pools.reset();
+        self.database_users.reset();
+    }
+
+    #[tracing::instrument(skip(self, client))]
+    pub async fn update(&self, client: &api::API) -> Result<(), GetResouceError> {
+        let databases = client.load_resource::<Databases>().await?;
+
+        let mut details = Vec::with_capacity(databases.len());
+        let mut errors = Vec::new();
+        for database in databases.iter() {
+            let mut replicas = None;
+            if database.supports_replicas() {
+                match DatabaseReplicas::load_for(client, &database.id).await {
+                    Ok(r) => replicas = Some(r.len()),
+                    Err(e) => {
+                        tracing::error!("Loading Database-Replicas for {:?}: {:?}", database.id, e);
+                        errors.push(e);
+                    }
+                }
+            }
+
+            let mut pools = None;
+            if database.supports_connection_pools() {
+                match DatabasePools::load_for(client, &database.id).await {
+                    Ok(p) => pools = Some(p.len()),
+                    Err(e) => {
+                        tracing::error!("Loading Database-Pools for {:?}: {:?}", database.id, e);
+                        errors.push(e);
+                    }
+                }
+            }
+
+            details.push((replicas, pools));
+        }
+
+        self.clear_metrics();
+
+        for (database, (replicas, pools)) in databases.iter().zip(details) {
+            let database_labels = labels! {
+                "id" => database.id.as_ref(),
+                "name" => database.name.as_ref(),
+                "engine" => database.engine.as_ref(),
+            };
+            let database_info_labels = labels! {
+                "id" => database.id.as_ref(),
+                "name" => database.name.as_ref(),
+                "engine" => database.engine.as_ref(),
+                "version" => database.version.as_ref(),
+                "region" => database.region.as_ref(),
+                "size" => database.size.as_ref(),
+                "status" => database.status.as_ref(),
+            };
+
+            let up = match database.status.as_str() {
+                "online" => 1,
+                _ => 0,
+            };
+
+            self.database.with(&database_info_labels).set(1);
+            self.database_up.with(&database_labels).set(up);
+            self.database_nodes
+                .with(&database_labels)
+                .set(database.num_nodes as i64);
+            self.database_storage_size_bytes
+                .with(&database_labels)
+                .set((database.storage_size_mib * 1024 * 1024) as i64);
+
+            if let Some(window) = database.maintenance_window.as_ref() {
+                let window_labels = labels! {
+                    "id" => database.id.as_ref(),
+                    "name" => database.name.as_ref(),
+                    "engine" => database.engine.as_ref(),
+                    "day" => window.day.as_ref(),
+                    "hour" => window.hour.as_ref(),
+                };
+
+                self.database_maintenance_window.with(&window_labels).set(1);
+                self.database_maintenance_pending
+                    .with(&database_labels)
+                    .set(window.pending as i64);
+            }
+
+            if let Some(users) = database.users.as_ref() {
+                self.database_users
+                    .with(&database_labels)
+                    .set(users.len() as i64);
+            }
+
+            if let Some(replicas) = replicas {
+                self.database_replicas
+                    .with(&database_labels)
+                    .set(replicas as i64);
+            }
+            if let Some(pools) = pools {
+                self.database_connection_pools
+                    .with(&database_labels)
+                    .set(pools as i64);
+            }
+        }
+
+        GetResouceError::partial(errors)
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::BTreeMap;
+
+use prometheus::{core, labels, IntGaugeVec};
+
+use crate::api::{self, DomainRecords, Domains, GetResouceError};
+
+/// The Metrics for the Domains and their DNS-Records of a single Account
+pub struct Metrics {
+    domain_ttl: IntGaugeVec,
+    domain_records: IntGaugeVec,
+    domain_record: IntGaugeVec,
+}
+
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            domain_ttl: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "domain_ttl",
+                    "The TTL of the Zone for a given Domain",
+                ),
+                &["domain"],
+            )
+            .unwrap(),
+            domain_records: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "domain_records",
+                    "The Number of DNS-Records of a given Type for a given Domain",
+                ),
+                &["domain", "type"],
+            )
+            .unwrap(),
+            domain_record: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "domain_record_info",
+                    "Information about a DNS-Record",
+                ),
+                &["domain", "id", "type", "name", "data", "ttl"],
+            )
+            .unwrap(),
+        }
+    }
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![
+            Box::new(self.domain_ttl.clone()),
+            Box::new(self.domain_records.clone()),
+            Box::new(self.domain_record.clone()),
+        ]
+    }
+
+    #[tracing::instrument(skip(self, client))]
+    pub async fn update(&self, client: &api::API) -> Result<(), GetResouceError> {
+        let domains = client.load_resource::<Domains>().await?;
+
+        let mut domain_records = Vec::with_capacity(domains.len());
+        let mut errors = Vec::new();
+        for domain in domains.iter() {
+            let records = match DomainRecords::load_for(client, &domain.name).await {
+                Ok(records) => Some(records),
+                Err(e) => {
+                    tracing::error!("Loading DNS-Records for {:?}: {:?}", domain.name, e);
+                    errors.push(e);
+                    None
+                }
+            };
+            domain_records.push(records);
+        }
+
+        self.domain_ttl.reset();
+        self.domain_records.reset();
+        self.domain_record.reset();
+
+        for (domain, records) in domains.iter().zip(domain_records.iter()) {
+            if let Some(ttl) = domain.ttl {
+                let domain_labels = labels! {
+                    "domain" => domain.name.as_ref(),
+                };
+
+                self.domain_ttl.with(&domain_labels).set(ttl as i64);
+            }
+
+            let records = match records {
+                Some(records) => records,
+                None => continue,
+            };
+
+            let mut counts: BTreeMap<&str, i64> = BTreeMap::new();
+            for record in records.iter() {
+                *counts.entry(record.ty.as_str()).or_default() += 1;
+
+                let id_str = record.id.to_string();
+                let ttl_str = record.ttl.to_string();
+
+                let record_labels = labels! {
+                    "domain" => domain.name.as_ref(),
+                    "id" => id_str.as_ref(),
+                    "type" => record.ty.as_ref(),
+                    "name" => record.name.as_ref(),
+                    "data" => record.data.as_ref(),
+                    "ttl" => ttl_str.as_ref(),
+                };
+
+                self.domain_record.with(&record_labels).set(1);
+            }
+
+            for (ty, count) in counts {
+                let count_labels = labels! {
+                    "domain" => domain.name.as_ref(),
+                    "type" => ty,
+                };
+
+                self.domain_records.with(&count_labels).set(count);
+            }
+        }
+
+        GetResouceError::partial(errors)
+    }
+}
@@ -1,121 +1,154 @@
-use lazy_static::lazy_static;
-use prometheus::{labels, GaugeVec, IntGaugeVec, Opts, Registry};
+use prometheus::{core, labels, GaugeVec, IntGaugeVec};
 
-use crate::api::{self, DropletStatus, Droplets};
+use crate::{
+    api::{self, DropletStatus, Droplets, GetResouceError},
+    sd,
+};
 
-lazy_static! {
-    static ref DROPLET_UP: IntGaugeVec = IntGaugeVec::new(
-        Opts::new("droplet_up", "If a given Droplet is currently running"),
-        &["id", "name", "region"]
-    )
-    .unwrap();
-    static ref DROPLET_VCPUS: IntGaugeVec = IntGaugeVec::new(
-        Opts::new("droplet_vcpus", "The Number of VCPUs for a given Droplet"),
-        &["id", "name", "region"]
-    )
-    .unwrap();
-    static ref DROPLET_MEMORY: IntGaugeVec = IntGaugeVec::new(
-        Opts::new("droplet_memory", "The Memory for a given Droplet"),
-        &["id", "name", "region"]
-    )
-    .unwrap();
-    static ref DROPLET_DISK: IntGaugeVec = IntGaugeVec::new(
-        Opts::new("droplet_disk", "The Disk size for a given Droplet"),
-        &["id", "name", "region"]
-    )
-    .unwrap();
-    static ref DROPLET_TRANSFER: GaugeVec = GaugeVec::new(
-        Opts::new("droplet_transfer", "The Transfer for a given Droplet"),
-        &["id", "name", "region"]
-    )
-    .unwrap();
-    static ref DROPLET_PRICE_MONTHLY: GaugeVec = GaugeVec::new(
-        Opts::new(
-            "droplet_price_monthly",
-            "The Monthly Price for a given Droplet"
-        ),
-        &["id", "name", "region"]
-    )
-    .unwrap();
-    static ref DROPLET_PRICE_HOURLY: GaugeVec = GaugeVec::new(
-        Opts::new(
-            "droplet_price_hourly",
-            "The Hourly Price for a given Droplet"
-        ),
-        &["id", "name", "region"]
-    )
-    .unwrap();
+/// The Metrics for the Droplets and their Sizing of a single Account
+pub struct Metrics {
+    droplet_up: IntGaugeVec,
+    droplet_vcpus: IntGaugeVec,
+    droplet_memory: IntGaugeVec,
+    droplet_disk: IntGaugeVec,
+    droplet_transfer: GaugeVec,
+    droplet_price_monthly: GaugeVec,
+    droplet_price_hourly: GaugeVec,
 }
 
-pub fn register_metrics(registry: &Registry) {
-    registry.register(Box::new(DROPLET_UP.clone())).unwrap();
-    registry.register(Box::new(DROPLET_VCPUS.clone())).unwrap();
-    registry.register(Box::new(DROPLET_MEMORY.clone())).unwrap();
-    registry.register(Box::new(DROPLET_DISK.clone())).unwrap();
-    registry
-        .register(Box::new(DROPLET_TRANSFER.clone()))
-        .unwrap();
-    registry
-        .register(Box::new(DROPLET_PRICE_MONTHLY.clone()))
-        .unwrap();
-    registry
-        .register(Box::new(DROPLET_PRICE_HOURLY.clone()))
-        .unwrap();
-}
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            droplet_up: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "droplet_up",
+                    "If a given Droplet is currently running",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            droplet_vcpus: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "droplet_vcpus",
+                    "The Number of VCPUs for a given Droplet",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            droplet_memory: IntGaugeVec::new(
+                super::opts(account, "droplet_memory", "The Memory for a given Droplet"),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            droplet_disk: IntGaugeVec::new(
+                super::opts(account, "droplet_disk", "The Disk size for a given Droplet"),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            droplet_transfer: GaugeVec::new(
+                super::opts(
+                    account,
+                    "droplet_transfer",
+                    "The Transfer for a given Droplet",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            droplet_price_monthly: GaugeVec::new(
+                super::opts(
+                    account,
+                    "droplet_price_monthly",
+                    "The Monthly Price for a given Droplet",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            droplet_price_hourly: GaugeVec::new(
+                super::opts(
+                    account,
+                    "droplet_price_hourly",
+                    "The Hourly Price for a given Droplet",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+        }
+    }
 
-fn clear_metrics() {
-    DROPLET_UP.reset();
-    DROPLET_VCPUS.reset();
-    DROPLET_MEMORY.reset();
-    DROPLET_DISK.reset();
-    DROPLET_TRANSFER.reset();
-    DROPLET_PRICE_MONTHLY.reset();
-    DROPLET_PRICE_HOURLY.reset();
-}
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![
+            Box::new(self.droplet_up.clone()),
+            Box::new(self.droplet_vcpus.clone()),
+            Box::new(self.droplet_memory.clone()),
+            Box::new(self.droplet_disk.clone()),
+            Box::new(self.droplet_transfer.clone()),
+            Box::new(self.droplet_price_monthly.clone()),
+            Box::new(self.droplet_price_hourly.clone()),
+        ]
+    }
 
-#[tracing::instrument(skip(client))]
-pub async fn update(client: &api::API) {
-    let droplets = match client.load_resource::<Droplets>().await {
-        Ok(d) => d,
-        Err(e) => {
-            tracing::error!("Loading-Droplets: {:?}", e);
-            return;
-        }
-    };
+    fn clear_metrics(&self) {
+        self.droplet_up.reset();
+        self.droplet_vcpus.reset();
+        self.droplet_memory.reset();
+        self.droplet_disk.reset();
+        self.droplet_transfer.reset();
+        self.droplet_price_monthly.reset();
+        self.droplet_price_hourly.reset();
+    }
+
+    #[tracing::instrument(skip(self, client, discovery))]
+    pub async fn update(
+        &self,
+        client: &api::API,
+        discovery: &sd::Discovery,
+    ) -> Result<(), GetResouceError> {
+        let droplets = client.load_resource::<Droplets>().await?;
+
+        self.clear_metrics();
 
-    clear_metrics();
+        for droplet in droplets.iter() {
+            let id = droplet.id;
+            let id_str = id.to_string();
 
-    for droplet in droplets.iter() {
-        let id = droplet.id;
-        let id_str = id.to_string();
+            let up = match &droplet.status {
+                DropletStatus::Active => 1,
+                _ => 0,
+            };
 
-        let up = match &droplet.status {
-            DropletStatus::Active => 1,
-            _ => 0,
-        };
+            let droplet_labels = labels! {
+                "id" => id_str.as_ref(),
+                "name" => droplet.name.as_ref(),
+                "region" => droplet.region.slug.as_ref(),
+            };
+
+            self.droplet_up.with(&droplet_labels).set(up);
+            self.droplet_vcpus
+                .with(&droplet_labels)
+                .set(droplet.vcpus as i64);
+            self.droplet_memory
+                .with(&droplet_labels)
+                .set(droplet.memory as i64);
+            self.droplet_disk
+                .with(&droplet_labels)
+                .set(droplet.disk as i64);
+            self.droplet_transfer
+                .with(&droplet_labels)
+                .set(droplet.size.transfer);
+            self.droplet_price_monthly
+                .with(&droplet_labels)
+                .set(droplet.size.price_monthly);
+            self.droplet_price_hourly
+                .with(&droplet_labels)
+                .set(droplet.size.price_hourly);
+        }
 
-        let droplet_labels = labels! {
-            "id" => id_str.as_ref(),
-            "name" => droplet.name.as_ref(),
-            "region" => droplet.region.slug.as_ref(),
-        };
+        discovery.update_droplets(client.name(), droplets);
 
-        DROPLET_UP.with(&droplet_labels).set(up);
-        DROPLET_VCPUS
-            .with(&droplet_labels)
-            .set(droplet.vcpus as i64);
-        DROPLET_MEMORY
-            .with(&droplet_labels)
-            .set(droplet.memory as i64);
-        DROPLET_DISK.with(&droplet_labels).set(droplet.disk as i64);
-        DROPLET_TRANSFER
-            .with(&droplet_labels)
-            .set(droplet.size.transfer);
-        DROPLET_PRICE_MONTHLY
-            .with(&droplet_labels)
-            .set(droplet.size.price_monthly);
-        DROPLET_PRICE_HOURLY
-            .with(&droplet_labels)
-            .set(droplet.size.price_hourly);
+        Ok(())
     }
 }
@@ -0,0 +1,174 @@
+use prometheus::{core, labels, IntGaugeVec};
+
+use crate::{
+    api::{self, Droplets, Firewalls, GetResouceError},
+    sd,
+};
+
+/// The Metrics for the Cloud-Firewalls of a single Account
+pub struct Metrics {
+    firewall: IntGaugeVec,
+    firewall_inbound_rules: IntGaugeVec,
+    firewall_outbound_rules: IntGaugeVec,
+    firewall_droplet: IntGaugeVec,
+    firewall_tag: IntGaugeVec,
+    droplet_firewalls: IntGaugeVec,
+}
+
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            firewall: IntGaugeVec::new(
+                super::opts(account, "firewall_info", "Information about a Firewall"),
+                &["id", "name", "status"],
+            )
+            .unwrap(),
+            firewall_inbound_rules: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "firewall_inbound_rules",
+                    "The Number of Rules for incoming Traffic of a given Firewall",
+                ),
+                &["id", "name"],
+            )
+            .unwrap(),
+            firewall_outbound_rules: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "firewall_outbound_rules",
+                    "The Number of Rules for outgoing Traffic of a given Firewall",
+                ),
+                &["id", "name"],
+            )
+            .unwrap(),
+            firewall_droplet: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "firewall_droplet",
+                    "A Droplet to which a given Firewall is directly applied",
+                ),
+                &["id", "name", "droplet_id"],
+            )
+            .unwrap(),
+            firewall_tag: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "firewall_tag",
+                    "A Tag of Droplets to which a given Firewall is applied",
+                ),
+                &["id", "name", "tag"],
+            )
+            .unwrap(),
+            droplet_firewalls: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "droplet_firewalls",
+                    "The Number of Firewalls applied to a given Droplet",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+        }
+    }
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![
+            Box::new(self.firewall.clone()),
+            Box::new(self.firewall_inbound_rules.clone()),
+            Box::new(self.firewall_outbound_rules.clone()),
+            Box::new(self.firewall_droplet.clone()),
+            Box::new(self.firewall_tag.clone()),
+            Box::new(self.droplet_firewalls.clone()),
+        ]
+    }
+
+    fn clear_metrics(&self) {
+        self.firewall.reset();
+        self.firewall_inbound_rules.reset();
+        self.firewall_outbound_rules.reset();
+        self.firewall_droplet.reset();
+        self.firewall_tag.reset();
+        self.droplet_firewalls.reset();
+    }
+
+    /// Loads the Firewalls and updates the Metrics.
+    ///
+    /// The Droplets last loaded by the Droplets-Collector are reused. They are only loaded here,
+    /// if that Collector is disabled or has not loaded them for this Account yet.
+    #[tracing::instrument(skip(self, client, discovery))]
+    pub async fn update(
+        &self,
+        client: &api::API,
+        discovery: &sd::Discovery,
+    ) -> Result<(), GetResouceError> {
+        let firewalls = client.load_resource::<Firewalls>().await?;
+        let droplets = match discovery.droplets(client.name()) {
+            Some(droplets) => droplets,
+            None => client.load_resource::<Droplets>().await?,
+        };
+
+        self.clear_metrics();
+
+        for firewall in firewalls.iter() {
+            let firewall_labels = labels! {
+                "id" => firewall.id.as_ref(),
+                "name" => firewall.name.as_ref(),
+            };
+            let firewall_info_labels = labels! {
+                "id" => firewall.id.as_ref(),
+                "name" => firewall.name.as_ref(),
+                "status" => firewall.status.as_ref(),
+            };
+
+            self.firewall.with(&firewall_info_labels).set(1);
+            self.firewall_inbound_rules
+                .with(&firewall_labels)
+                .set(firewall.inbound_rules.len() as i64);
+            self.firewall_outbound_rules
+                .with(&firewall_labels)
+                .set(firewall.outbound_rules.len() as i64);
+
+            for droplet_id in firewall.droplet_ids.iter() {
+                let droplet_id_str = droplet_id.to_string();
+
+                let firewall_droplet_labels = labels! {
+                    "id" => firewall.id.as_ref(),
+                    "name" => firewall.name.as_ref(),
+                    "droplet_id" => droplet_id_str.as_ref(),
+                };
+
+                self.firewall_droplet.with(&firewall_droplet_labels).set(1);
+            }
+
+            for tag in firewall.tags.iter() {
+                let firewall_tag_labels = labels! {
+                    "id" => firewall.id.as_ref(),
+                    "name" => firewall.name.as_ref(),
+                    "tag" => tag.as_ref(),
+                };
+
+                self.firewall_tag.with(&firewall_tag_labels).set(1);
+            }
+        }
+
+        for droplet in droplets.iter() {
+            let id_str = droplet.id.to_string();
+
+            let droplet_labels = labels! {
+                "id" => id_str.as_ref(),
+                "name" => droplet.name.as_ref(),
+                "region" => droplet.region.slug.as_ref(),
+            };
+
+            let count = firewalls.iter().filter(|f| f.applies_to(droplet)).count();
+
+            self.droplet_firewalls
+                .with(&droplet_labels)
+                .set(count as i64);
+        }
+
+        Ok(())
+    }
+}
@@ -1,38 +1,48 @@
-use lazy_static::lazy_static;
-use prometheus::{labels, IntGaugeVec, Opts, Registry};
+use prometheus::{core, labels, IntGaugeVec};
 
-use crate::api::{self, FloatingIps};
+use crate::api::{self, FloatingIps, GetResouceError};
 
-lazy_static! {
-    static ref FLOATING_IP: IntGaugeVec = IntGaugeVec::new(
-        Opts::new("floating_ip", "Information about a Floating-IP"),
-        &["ip", "region"]
-    )
-    .unwrap();
+/// The Metrics for the Floating-IPs of a single Account
+pub struct Metrics {
+    floating_ip: IntGaugeVec,
 }
 
-pub fn register_metrics(registry: &Registry) {
-    registry.register(Box::new(FLOATING_IP.clone())).unwrap();
-}
-
-#[tracing::instrument(skip(client))]
-pub async fn update(client: &api::API) {
-    let floating_ips = match client.load_resource::<FloatingIps>().await {
-        Ok(f) => f,
-        Err(e) => {
-            tracing::error!("Loading Floating-IPs: {:?}", e);
-            return;
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            floating_ip: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "floating_ip_info",
+                    "Information about a Floating-IP",
+                ),
+                &["ip", "region"],
+            )
+            .unwrap(),
         }
-    };
+    }
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![Box::new(self.floating_ip.clone())]
+    }
+
+    #[tracing::instrument(skip(self, client))]
+    pub async fn update(&self, client: &api::API) -> Result<(), GetResouceError> {
+        let floating_ips = client.load_resource::<FloatingIps>().await?;
 
-    FLOATING_IP.reset();
+        self.floating_ip.reset();
 
-    for floating_ip in floating_ips.iter() {
-        let floating_ip_labels = labels! {
-            "ip" => floating_ip.ip.as_ref(),
-            "region" => floating_ip.region.slug.as_ref(),
-        };
+        for floating_ip in floating_ips.iter() {
+            let floating_ip_labels = labels! {
+                "ip" => floating_ip.ip.as_ref(),
+                "region" => floating_ip.region.slug.as_ref(),
+            };
+
+            self.floating_ip.with(&floating_ip_labels).set(1);
+        }
 
-        FLOATING_IP.with(&floating_ip_labels).set(1);
+        Ok(())
     }
 }
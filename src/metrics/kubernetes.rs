@@ -0,0 +1,249 @@
+use prometheus::{core, labels, IntGaugeVec};
+
+use crate::api::{self, GetResouceError, KubernetesClusters, KubernetesUpgrades};
+
+/// The Metrics for the Kubernetes-Clusters and their Node-Pools of a single Account
+pub struct Metrics {
+    kubernetes_cluster: IntGaugeVec,
+    kubernetes_cluster_up: IntGaugeVec,
+    kubernetes_cluster_auto_upgrade: IntGaugeVec,
+    kubernetes_cluster_ha: IntGaugeVec,
+    kubernetes_cluster_upgrade_available: IntGaugeVec,
+    kubernetes_node_pool_nodes: IntGaugeVec,
+    kubernetes_node_pool_min_nodes: IntGaugeVec,
+    kubernetes_node_pool_max_nodes: IntGaugeVec,
+    kubernetes_node_pool_auto_scale: IntGaugeVec,
+    kubernetes_node_up: IntGaugeVec,
+}
+
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            kubernetes_cluster: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "kubernetes_cluster_info",
+                    "Information about a Kubernetes-Cluster",
+                ),
+                &["id", "name", "region", "version", "state"],
+            )
+            .unwrap(),
+            kubernetes_cluster_up: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "kubernetes_cluster_up",
+                    "If a given Kubernetes-Cluster is currently running",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            kubernetes_cluster_auto_upgrade: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "kubernetes_cluster_auto_upgrade",
+                    "If a given Kubernetes-Cluster will be upgraded automatically",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            kubernetes_cluster_ha: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "kubernetes_cluster_ha",
+                    "If a given Kubernetes-Cluster has a highly available Control-Plane",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            kubernetes_cluster_upgrade_available: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "kubernetes_cluster_upgrade_available",
+                    "If there is an Upgrade available for a given Kubernetes-Cluster",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            kubernetes_node_pool_nodes: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "kubernetes_node_pool_nodes",
+                    "The Number of Nodes in a given Node-Pool",
+                ),
+                &["cluster_id", "id", "name", "size"],
+            )
+            .unwrap(),
+            kubernetes_node_pool_min_nodes: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "kubernetes_node_pool_min_nodes",
+                    "The minimum Number of Nodes in a given auto scaled Node-Pool",
+                ),
+                &["cluster_id", "id", "name", "size"],
+            )
+            .unwrap(),
+            kubernetes_node_pool_max_nodes: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "kubernetes_node_pool_max_nodes",
+                    "The maximum Number of Nodes in a given auto scaled Node-Pool",
+                ),
+                &["cluster_id", "id", "name", "size"],
+            )
+            .unwrap(),
+            kubernetes_node_pool_auto_scale: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "kubernetes_node_pool_auto_scale",
+                    "If a given Node-Pool is scaled automatically",
+                ),
+                &["cluster_id", "id", "name", "size"],
+            )
+            .unwrap(),
+            kubernetes_node_up: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "kubernetes_node_up",
+                    "If a given Kubernetes-Node is currently running",
+                ),
+                &[
+                    "cluster_id",
+                    "node_pool_id",
+                    "id",
+                    "name",
+                    "droplet_id",
+                    "state",
+                ],
+            )
+            .unwrap(),
+        }
+    }
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![
+            Box::new(self.kubernetes_cluster.clone()),
+            Box::new(self.kubernetes_cluster_up.clone()),
+            Box::new(self.kubernetes_cluster_auto_upgrade.clone()),
+            Box::new(self.kubernetes_cluster_ha.clone()),
+            Box::new(self.kubernetes_cluster_upgrade_available.clone()),
+            Box::new(self.kubernetes_node_pool_nodes.clone()),
+            Box::new(self.kubernetes_node_pool_min_nodes.clone()),
+            Box::new(self.kubernetes_node_pool_max_nodes.clone()),
+            Box::new(self.kubernetes_node_pool_auto_scale.clone()),
+            Box::new(self.kubernetes_node_up.clone()),
+        ]
+    }
+
+    fn clear_metrics(&self) {
+        self.kubernetes_cluster.reset();
+        self.kubernetes_cluster_up.reset();
+        self.kubernetes_cluster_auto_upgrade.reset();
+        self.kubernetes_cluster_ha.reset();
+        self.kubernetes_cluster_upgrade_available.reset();
+        self.kubernetes_node_pool_nodes.reset();
+        self.kubernetes_node_pool_min_nodes.reset();
+        self.kubernetes_node_pool_max_nodes.reset();
+        self.kubernetes_node_pool_auto_scale.reset();
+        self.kubernetes_node_up.reset();
+    }
+
+    #[tracing::instrument(skip(self, client))]
+    pub async fn update(&self, client: &api::API) -> Result<(), GetResouceError> {
+        let clusters = client.load_resource::<KubernetesClusters>().await?;
+
+        let mut upgrades = Vec::with_capacity(clusters.len());
+        let mut errors = Vec::new();
+        for cluster in clusters.iter() {
+            let available = match KubernetesUpgrades::load_for(client, &cluster.id).await {
+                Ok(upgrades) => Some(!upgrades.is_empty()),
+                Err(e) => {
+                    tracing::error!("Loading Kubernetes-Upgrades for {:?}: {:?}", cluster.id, e);
+                    errors.push(e);
+                    None
+                }
+            };
+            upgrades.push(available);
+        }
+
+        self.clear_metrics();
+
+        for (cluster, upgrade_available) in clusters.iter().zip(upgrades) {
+            let cluster_labels = labels! {
+                "id" => cluster.id.as_ref(),
+                "name" => cluster.name.as_ref(),
+                "region" => cluster.region.as_ref(),
+            };
+            let cluster_info_labels = labels! {
+                "id" => cluster.id.as_ref(),
+                "name" => cluster.name.as_ref(),
+                "region" => cluster.region.as_ref(),
+                "version" => cluster.version.as_ref(),
+                "state" => cluster.status.state.as_ref(),
+            };
+
+            let up = match cluster.status.state.as_str() {
+                "running" => 1,
+                _ => 0,
+            };
+
+            self.kubernetes_cluster.with(&cluster_info_labels).set(1);
+            self.kubernetes_cluster_up.with(&cluster_labels).set(up);
+            self.kubernetes_cluster_auto_upgrade
+                .with(&cluster_labels)
+                .set(cluster.auto_upgrade as i64);
+            self.kubernetes_cluster_ha
+                .with(&cluster_labels)
+                .set(cluster.ha as i64);
+
+            if let Some(available) = upgrade_available {
+                self.kubernetes_cluster_upgrade_available
+                    .with(&cluster_labels)
+                    .set(available as i64);
+            }
+
+            for node_pool in cluster.node_pools.iter() {
+                let node_pool_labels = labels! {
+                    "cluster_id" => cluster.id.as_ref(),
+                    "id" => node_pool.id.as_ref(),
+                    "name" => node_pool.name.as_ref(),
+                    "size" => node_pool.size.as_ref(),
+                };
+
+                self.kubernetes_node_pool_nodes
+                    .with(&node_pool_labels)
+                    .set(node_pool.count as i64);
+                self.kubernetes_node_pool_min_nodes
+                    .with(&node_pool_labels)
+                    .set(node_pool.min_nodes as i64);
+                self.kubernetes_node_pool_max_nodes
+                    .with(&node_pool_labels)
+                    .set(node_pool.max_nodes as i64);
+                self.kubernetes_node_pool_auto_scale
+                    .with(&node_pool_labels)
+                    .set(node_pool.auto_scale as i64);
+
+                for node in node_pool.nodes.iter() {
+                    let node_labels = labels! {
+                        "cluster_id" => cluster.id.as_ref(),
+                        "node_pool_id" => node_pool.id.as_ref(),
+                        "id" => node.id.as_ref(),
+                        "name" => node.name.as_ref(),
+                        "droplet_id" => node.droplet_id.as_ref(),
+                        "state" => node.status.state.as_ref(),
+                    };
+
+                    let up = match node.status.state.as_str() {
+                        "running" => 1,
+                        _ => 0,
+                    };
+
+                    self.kubernetes_node_up.with(&node_labels).set(up);
+                }
+            }
+        }
+
+        GetResouceError::partial(errors)
+    }
+}
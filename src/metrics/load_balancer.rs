@@ -0,0 +1,142 @@
+use prometheus::{core, labels, IntGaugeVec};
+
+use crate::api::{self, GetResouceError, LoadBalancers};
+
+/// The Metrics for the Load-Balancers of a single Account
+pub struct Metrics {
+    load_balancer: IntGaugeVec,
+    load_balancer_up: IntGaugeVec,
+    load_balancer_size_unit: IntGaugeVec,
+    load_balancer_forwarding_rules: IntGaugeVec,
+    load_balancer_droplet: IntGaugeVec,
+}
+
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            load_balancer: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "load_balancer_info",
+                    "Information about a Load-Balancer",
+                ),
+                &["id", "name", "region", "ip", "algorithm", "size", "status"],
+            )
+            .unwrap(),
+            load_balancer_up: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "load_balancer_up",
+                    "If a given Load-Balancer is currently active",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            load_balancer_size_unit: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "load_balancer_size_unit",
+                    "The Number of Nodes of a given Load-Balancer",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            load_balancer_forwarding_rules: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "load_balancer_forwarding_rules",
+                    "The Number of Forwarding-Rules of a given Load-Balancer",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            load_balancer_droplet: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "load_balancer_droplet",
+                    "A Droplet assigned to a given Load-Balancer, always 1 as the API does not \
+                     report the Health of the Droplets",
+                ),
+                &["id", "name", "region", "droplet_id"],
+            )
+            .unwrap(),
+        }
+    }
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![
+            Box::new(self.load_balancer.clone()),
+            Box::new(self.load_balancer_up.clone()),
+            Box::new(self.load_balancer_size_unit.clone()),
+            Box::new(self.load_balancer_forwarding_rules.clone()),
+            Box::new(self.load_balancer_droplet.clone()),
+        ]
+    }
+
+    fn clear_metrics(&self) {
+        self.load_balancer.reset();
+        self.load_balancer_up.reset();
+        self.load_balancer_size_unit.reset();
+        self.load_balancer_forwarding_rules.reset();
+        self.load_balancer_droplet.reset();
+    }
+
+    #[tracing::instrument(skip(self, client))]
+    pub async fn update(&self, client: &api::API) -> Result<(), GetResouceError> {
+        let load_balancers = client.load_resource::<LoadBalancers>().await?;
+
+        self.clear_metrics();
+
+        for load_balancer in load_balancers.iter() {
+            let load_balancer_labels = labels! {
+                "id" => load_balancer.id.as_ref(),
+                "name" => load_balancer.name.as_ref(),
+                "region" => load_balancer.region.slug.as_ref(),
+            };
+            let load_balancer_info_labels = labels! {
+                "id" => load_balancer.id.as_ref(),
+                "name" => load_balancer.name.as_ref(),
+                "region" => load_balancer.region.slug.as_ref(),
+                "ip" => load_balancer.ip.as_ref(),
+                "algorithm" => load_balancer.algorithm.as_ref(),
+                "size" => load_balancer.size.as_ref(),
+                "status" => load_balancer.status.as_ref(),
+            };
+
+            let up = match load_balancer.status.as_str() {
+                "active" => 1,
+                _ => 0,
+            };
+
+            self.load_balancer.with(&load_balancer_info_labels).set(1);
+            self.load_balancer_up.with(&load_balancer_labels).set(up);
+            if let Some(size_unit) = load_balancer.size_unit {
+                self.load_balancer_size_unit
+                    .with(&load_balancer_labels)
+                    .set(size_unit as i64);
+            }
+            self.load_balancer_forwarding_rules
+                .with(&load_balancer_labels)
+                .set(load_balancer.forwarding_rules.len() as i64);
+
+            for droplet_id in load_balancer.droplet_ids.iter() {
+                let droplet_id_str = droplet_id.to_string();
+
+                let load_balancer_droplet_labels = labels! {
+                    "id" => load_balancer.id.as_ref(),
+                    "name" => load_balancer.name.as_ref(),
+                    "region" => load_balancer.region.slug.as_ref(),
+                    "droplet_id" => droplet_id_str.as_ref(),
+                };
+
+                self.load_balancer_droplet
+                    .with(&load_balancer_droplet_labels)
+                    .set(1);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,287 @@
+use futures_util::future;
+use prometheus::{core, labels, GaugeVec};
+use tokio::sync::Semaphore;
+
+use crate::api::{
+    self, Droplet, DropletMonitoring, DropletStatus, Droplets, GetResouceError, MonitoringSeries,
+};
+
+/// The Time-Range (in seconds) for which Samples are requested, only the latest Sample in it is
+/// exposed
+const WINDOW_SECONDS: i64 = 5 * 60;
+
+/// The Number of Requests to the Monitoring-API that are made at the same Time
+const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// A Metric of the Monitoring-API together with its additional Parameters
+type Query = (&'static str, &'static [(&'static str, &'static str)]);
+
+/// The Metrics that are requested for every Droplet
+const QUERIES: &[Query] = &[
+    ("memory_available", &[]),
+    ("memory_total", &[]),
+    ("load_1", &[]),
+    ("cpu", &[]),
+    ("filesystem_free", &[]),
+    (
+        "bandwidth",
+        &[("interface", "public"), ("direction", "inbound")],
+    ),
+    (
+        "bandwidth",
+        &[("interface", "public"), ("direction", "outbound")],
+    ),
+    (
+        "bandwidth",
+        &[("interface", "private"), ("direction", "inbound")],
+    ),
+    (
+        "bandwidth",
+        &[("interface", "private"), ("direction", "outbound")],
+    ),
+];
+
+/// The Metrics for the Utilisation of the Droplets of a single Account
+pub struct Metrics {
+    droplet_cpu_seconds: GaugeVec,
+    droplet_memory_available_bytes: GaugeVec,
+    droplet_memory_total_bytes: GaugeVec,
+    droplet_load1: GaugeVec,
+    droplet_filesystem_free_bytes: GaugeVec,
+    droplet_bandwidth_mbps: GaugeVec,
+}
+
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            droplet_cpu_seconds: GaugeVec::new(
+                super::opts(
+                    account,
+                    "droplet_cpu_seconds",
+                    "The CPU-Time spent in each Mode by a given Droplet",
+                ),
+                &["id", "name", "region", "mode"],
+            )
+            .unwrap(),
+            droplet_memory_available_bytes: GaugeVec::new(
+                super::opts(
+                    account,
+                    "droplet_memory_available_bytes",
+                    "The available Memory of a given Droplet in Bytes",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            droplet_memory_total_bytes: GaugeVec::new(
+                super::opts(
+                    account,
+                    "droplet_memory_total_bytes",
+                    "The total Memory of a given Droplet in Bytes",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            droplet_load1: GaugeVec::new(
+                super::opts(
+                    account,
+                    "droplet_load1",
+                    "The 1-Minute Load-Average of a given Droplet",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            droplet_filesystem_free_bytes: GaugeVec::new(
+                super::opts(
+                    account,
+                    "droplet_filesystem_free_bytes",
+                    "The free Space on a Filesystem of a given Droplet in Bytes",
+                ),
+                &["id", "name", "region", "device", "mountpoint"],
+            )
+            .unwrap(),
+            droplet_bandwidth_mbps: GaugeVec::new(
+                super::opts(
+                    account,
+                    "droplet_bandwidth_mbps",
+                    "The Bandwidth of a given Droplet in Megabits per second",
+                ),
+                &["id", "name", "region", "interface", "direction"],
+            )
+            .unwrap(),
+        }
+    }
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![
+            Box::new(self.droplet_cpu_seconds.clone()),
+            Box::new(self.droplet_memory_available_bytes.clone()),
+            Box::new(self.droplet_memory_total_bytes.clone()),
+            Box::new(self.droplet_load1.clone()),
+            Box::new(self.droplet_filesystem_free_bytes.clone()),
+            Box::new(self.droplet_bandwidth_mbps.clone()),
+        ]
+    }
+
+    fn clear_metrics(&self) {
+        self.droplet_cpu_seconds.reset();
+        self.droplet_memory_available_bytes.reset();
+        self.droplet_memory_total_bytes.reset();
+        self.droplet_load1.reset();
+        self.droplet_filesystem_free_bytes.reset();
+        self.droplet_bandwidth_mbps.reset();
+    }
+
+    #[tracing::instrument(skip(self, client))]
+    pub async fn update(&self, client: &api::API) -> Result<(), GetResouceError> {
+        let droplets = client.load_resource::<Droplets>().await?;
+
+        let end = chrono::Utc::now().timestamp();
+        let start = end - WINDOW_SECONDS;
+
+        // Droplets that are not running don't report any Metrics
+        let requests: Vec<_> = droplets
+            .iter()
+            .filter(|d| matches!(d.status, DropletStatus::Active))
+            .flat_map(|d| QUERIES.iter().map(move |query| (d, *query)))
+            .collect();
+
+        let permits = Semaphore::new(MAX_CONCURRENT_REQUESTS);
+        let results = future::join_all(
+            requests
+                .iter()
+                .map(|(droplet, query)| load(client, &permits, droplet.id, *query, start, end)),
+        )
+        .await;
+
+        self.clear_metrics();
+
+        let mut errors = Vec::new();
+        for ((droplet, (metric, params)), result) in requests.iter().zip(results) {
+            match result {
+                Ok(series) => self.set(droplet, metric, params, &series),
+                Err(e) => {
+                    tracing::error!(
+                        "Loading Monitoring-Metric {:?} for {:?}: {:?}",
+                        metric,
+                        droplet.id,
+                        e
+                    );
+                    errors.push(e);
+                }
+            }
+        }
+
+        GetResouceError::partial(errors)
+    }
+
+    /// Updates the Gauge for the given Metric of a Droplet with the latest loaded Samples
+    fn set(
+        &self,
+        droplet: &Droplet,
+        metric: &str,
+        params: &[(&str, &str)],
+        series: &[MonitoringSeries],
+    ) {
+        let id_str = droplet.id.to_string();
+
+        let droplet_labels = labels! {
+            "id" => id_str.as_ref(),
+            "name" => droplet.name.as_ref(),
+            "region" => droplet.region.slug.as_ref(),
+        };
+
+        let gauge = match metric {
+            "memory_available" => &self.droplet_memory_available_bytes,
+            "memory_total" => &self.droplet_memory_total_bytes,
+            "load_1" => &self.droplet_load1,
+            "cpu" => {
+                for cpu in series.iter() {
+                    let value = match cpu.latest() {
+                        Some(v) => v,
+                        None => continue,
+                    };
+
+                    let cpu_labels = labels! {
+                        "id" => id_str.as_ref(),
+                        "name" => droplet.name.as_ref(),
+                        "region" => droplet.region.slug.as_ref(),
+                        "mode" => cpu.label("mode"),
+                    };
+
+                    self.droplet_cpu_seconds.with(&cpu_labels).set(value);
+                }
+                return;
+            }
+            "filesystem_free" => {
+                for filesystem in series.iter() {
+                    let value = match filesystem.latest() {
+                        Some(v) => v,
+                        None => continue,
+                    };
+
+                    let filesystem_labels = labels! {
+                        "id" => id_str.as_ref(),
+                        "name" => droplet.name.as_ref(),
+                        "region" => droplet.region.slug.as_ref(),
+                        "device" => filesystem.label("device"),
+                        "mountpoint" => filesystem.label("mountpoint"),
+                    };
+
+                    self.droplet_filesystem_free_bytes
+                        .with(&filesystem_labels)
+                        .set(value);
+                }
+                return;
+            }
+            "bandwidth" => {
+                let param = |name: &str| {
+                    params
+                        .iter()
+                        .find(|(n, _)| *n == name)
+                        .map(|(_, v)| *v)
+                        .unwrap_or_default()
+                };
+
+                if let Some(value) = series.first().and_then(|s| s.latest()) {
+                    let bandwidth_labels = labels! {
+                        "id" => id_str.as_ref(),
+                        "name" => droplet.name.as_ref(),
+                        "region" => droplet.region.slug.as_ref(),
+                        "interface" => param("interface"),
+                        "direction" => param("direction"),
+                    };
+
+                    self.droplet_bandwidth_mbps
+                        .with(&bandwidth_labels)
+                        .set(value);
+                }
+                return;
+            }
+            _ => return,
+        };
+
+        if let Some(value) = series.first().and_then(|s| s.latest()) {
+            gauge.with(&droplet_labels).set(value);
+        }
+    }
+}
+
+/// Loads a single Metric for the Droplet with the given ID, once fewer than the maximum Number
+/// of Requests are running
+async fn load(
+    client: &api::API,
+    permits: &Semaphore,
+    id: u64,
+    (metric, params): Query,
+    start: i64,
+    end: i64,
+) -> Result<Vec<MonitoringSeries>, GetResouceError> {
+    let _permit = permits
+        .acquire()
+        .await
+        .expect("The Semaphore is never closed");
+
+    DropletMonitoring::load_for(client, metric, id, start, end, params).await
+}
@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use prometheus::{
+    core, exponential_buckets, labels, HistogramOpts, HistogramVec, IntCounterVec, IntGauge,
+};
+
+use crate::api::RateLimit;
+
+/// The Metrics about the Requests made to the DigitalOcean API for a single Account
+pub struct Metrics {
+    api_requests_total: IntCounterVec,
+    api_request_duration_seconds: HistogramVec,
+    api_response_size_bytes: HistogramVec,
+    api_ratelimit_limit: IntGauge,
+    api_ratelimit_remaining: IntGauge,
+}
+
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            api_requests_total: IntCounterVec::new(
+                super::opts(
+                    account,
+                    "api_requests_total",
+                    "The Number of Requests made to the DigitalOcean API",
+                ),
+                &["endpoint", "status"],
+            )
+            .unwrap(),
+            api_request_duration_seconds: HistogramVec::new(
+                HistogramOpts::from(super::opts(
+                    account,
+                    "api_request_duration_seconds",
+                    "The Time it took until the DigitalOcean API responded to a Request",
+                )),
+                &["endpoint"],
+            )
+            .unwrap(),
+            api_response_size_bytes: HistogramVec::new(
+                HistogramOpts::from(super::opts(
+                    account,
+                    "api_response_size_bytes",
+                    "The Size of the Responses received from the DigitalOcean API",
+                ))
+                .buckets(exponential_buckets(256.0, 4.0, 8).unwrap()),
+                &["endpoint"],
+            )
+            .unwrap(),
+            api_ratelimit_limit: IntGauge::with_opts(super::opts(
+                account,
+                "api_ratelimit_limit",
+                "The Number of Requests that can be made to the DigitalOcean API per Hour",
+            ))
+            .unwrap(),
+            api_ratelimit_remaining: IntGauge::with_opts(super::opts(
+                account,
+                "api_ratelimit_remaining",
+                "The Number of Requests that can still be made until the Rate-Limit resets",
+            ))
+            .unwrap(),
+        }
+    }
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![
+            Box::new(self.api_requests_total.clone()),
+            Box::new(self.api_request_duration_seconds.clone()),
+            Box::new(self.api_response_size_bytes.clone()),
+            Box::new(self.api_ratelimit_limit.clone()),
+            Box::new(self.api_ratelimit_remaining.clone()),
+        ]
+    }
+
+    /// Records a single Request to the given Endpoint-Template
+    pub fn observe_request(&self, endpoint: &str, status: &str, duration: Duration) {
+        self.api_requests_total
+            .with(&labels! { "endpoint" => endpoint, "status" => status, })
+            .inc();
+        self.api_request_duration_seconds
+            .with(&labels! { "endpoint" => endpoint, })
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Records the Size of a Response received from the given Endpoint-Template
+    pub fn observe_response_size(&self, endpoint: &str, size: usize) {
+        self.api_response_size_bytes
+            .with(&labels! { "endpoint" => endpoint, })
+            .observe(size as f64);
+    }
+
+    /// Records the last seen Rate-Limit
+    pub fn observe_rate_limit(&self, rate_limit: &RateLimit) {
+        self.api_ratelimit_limit.set(rate_limit.limit as i64);
+        self.api_ratelimit_remaining
+            .set(rate_limit.remaining as i64);
+    }
+}
@@ -0,0 +1,112 @@
+use prometheus::{core, labels, IntGaugeVec};
+
+use crate::api::{self, GetResouceError, Volumes};
+
+/// The Metrics for the Block-Storage Volumes of a single Account
+pub struct Metrics {
+    volume: IntGaugeVec,
+    volume_size_bytes: IntGaugeVec,
+    volume_attachments: IntGaugeVec,
+    volume_droplet: IntGaugeVec,
+}
+
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            volume: IntGaugeVec::new(
+                super::opts(account, "volume_info", "Information about a Volume"),
+                &["id", "name", "region", "filesystem_type"],
+            )
+            .unwrap(),
+            volume_size_bytes: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "volume_size_bytes",
+                    "The Size of a given Volume in Bytes",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            volume_attachments: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "volume_attachments",
+                    "The Number of Droplets a given Volume is attached to",
+                ),
+                &["id", "name", "region"],
+            )
+            .unwrap(),
+            volume_droplet: IntGaugeVec::new(
+                super::opts(
+                    account,
+                    "volume_droplet",
+                    "A Droplet to which a given Volume is attached",
+                ),
+                &["id", "name", "region", "droplet_id"],
+            )
+            .unwrap(),
+        }
+    }
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![
+            Box::new(self.volume.clone()),
+            Box::new(self.volume_size_bytes.clone()),
+            Box::new(self.volume_attachments.clone()),
+            Box::new(self.volume_droplet.clone()),
+        ]
+    }
+
+    fn clear_metrics(&self) {
+        self.volume.reset();
+        self.volume_size_bytes.reset();
+        self.volume_attachments.reset();
+        self.volume_droplet.reset();
+    }
+
+    #[tracing::instrument(skip(self, client))]
+    pub async fn update(&self, client: &api::API) -> Result<(), GetResouceError> {
+        let volumes = client.load_resource::<Volumes>().await?;
+
+        self.clear_metrics();
+
+        for volume in volumes.iter() {
+            let volume_labels = labels! {
+                "id" => volume.id.as_ref(),
+                "name" => volume.name.as_ref(),
+                "region" => volume.region.slug.as_ref(),
+            };
+            let volume_info_labels = labels! {
+                "id" => volume.id.as_ref(),
+                "name" => volume.name.as_ref(),
+                "region" => volume.region.slug.as_ref(),
+                "filesystem_type" => volume.filesystem_type.as_ref(),
+            };
+
+            self.volume.with(&volume_info_labels).set(1);
+            self.volume_size_bytes
+                .with(&volume_labels)
+                .set((volume.size_gigabytes * 1024 * 1024 * 1024) as i64);
+            self.volume_attachments
+                .with(&volume_labels)
+                .set(volume.droplet_ids.len() as i64);
+
+            for droplet_id in volume.droplet_ids.iter() {
+                let droplet_id_str = droplet_id.to_string();
+
+                let volume_droplet_labels = labels! {
+                    "id" => volume.id.as_ref(),
+                    "name" => volume.name.as_ref(),
+                    "region" => volume.region.slug.as_ref(),
+                    "droplet_id" => droplet_id_str.as_ref(),
+                };
+
+                self.volume_droplet.with(&volume_droplet_labels).set(1);
+            }
+        }
+
+        Ok(())
+    }
+}
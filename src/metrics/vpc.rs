@@ -1,38 +1,44 @@
-use lazy_static::lazy_static;
-use prometheus::{labels, IntGaugeVec, Opts, Registry};
+use prometheus::{core, labels, IntGaugeVec};
 
-use crate::api::{self, VPCs};
+use crate::api::{self, GetResouceError, VPCs};
 
-lazy_static! {
-    static ref VPC: IntGaugeVec = IntGaugeVec::new(
-        Opts::new("vpc", "Information about a VPC"),
-        &["id", "name", "region", "ip_range"]
-    )
-    .unwrap();
+/// The Metrics for the VPCs of a single Account
+pub struct Metrics {
+    vpc: IntGaugeVec,
 }
 
-pub fn register_metrics(registry: &Registry) {
-    registry.register(Box::new(VPC.clone())).unwrap();
-}
-
-#[tracing::instrument(skip(client))]
-pub async fn update(client: &api::API) {
-    let vpcs = match client.load_resource::<VPCs>().await {
-        Ok(v) => v,
-        Err(e) => {
-            tracing::error!("Loading VPCs: {:?}", e);
-            return;
+impl Metrics {
+    /// Creates the Metrics for the given Account
+    pub fn new(account: &str) -> Self {
+        Self {
+            vpc: IntGaugeVec::new(
+                super::opts(account, "vpc_info", "Information about a VPC"),
+                &["id", "name", "region", "ip_range"],
+            )
+            .unwrap(),
         }
-    };
+    }
+
+    /// All the Metrics, which can be registered in a Registry
+    pub fn collectors(&self) -> Vec<Box<dyn core::Collector>> {
+        vec![Box::new(self.vpc.clone())]
+    }
+
+    #[tracing::instrument(skip(self, client))]
+    pub async fn update(&self, client: &api::API) -> Result<(), GetResouceError> {
+        let vpcs = client.load_resource::<VPCs>().await?;
 
-    for vpc in vpcs.iter() {
-        let vpc_labels = labels! {
-            "id" => vpc.id.as_ref(),
-            "name" => vpc.name.as_ref(),
-            "region" => vpc.region.as_ref(),
-            "ip_range" => vpc.ip_range.as_ref(),
-        };
+        for vpc in vpcs.iter() {
+            let vpc_labels = labels! {
+                "id" => vpc.id.as_ref(),
+                "name" => vpc.name.as_ref(),
+                "region" => vpc.region.as_ref(),
+                "ip_range" => vpc.ip_range.as_ref(),
+            };
+
+            self.vpc.with(&vpc_labels).set(1);
+        }
 
-        VPC.with(&vpc_labels).set(1);
+        Ok(())
     }
 }
@@ -0,0 +1,141 @@
+//! Allows for loading the Metrics when they are scraped, instead of periodically in the
+//! Background
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures_util::future;
+use prometheus::{
+    core::{self, Desc},
+    proto::MetricFamily,
+};
+
+use crate::{Collector, Exporter};
+
+/// The Times at which the Metrics have been refreshed
+#[derive(Debug, Default)]
+struct Refreshes {
+    /// The Time of the last Refresh
+    last: Option<Instant>,
+    /// The Time of the last successful Run of every Collector
+    collectors: HashMap<Collector, Instant>,
+}
+
+/// A Prometheus-Collector that runs the enabled Collectors of an Exporter whenever it is
+/// collected, so the Age of the Data matches the Scrape-Interval and no Requests are made while
+/// nobody is scraping.
+///
+/// The loaded Metrics are cached for at least the configured TTL and concurrent Scrapes share a
+/// single Refresh, instead of each loading the Metrics on their own. The Interval of every
+/// Collector is the minimum Age of its Metrics, so a Collector like the Balance is not run on
+/// every Scrape.
+///
+/// Collecting never makes any Requests itself, it only returns the Metrics as of the last
+/// Refresh. [`ScrapeCollector::refresh`] therefore has to be awaited before gathering the
+/// Registry, which works on any Tokio-Runtime.
+///
+/// Clones share the same Cache, so one Clone can be registered while another one is refreshed.
+pub struct ScrapeCollector {
+    /// The Exporter whose Collectors are run
+    exporter: Arc<Exporter>,
+    /// The minimum Time for which loaded Metrics are reused
+    cache_ttl: Duration,
+    /// The Times of the last Refreshes, the Lock is held while refreshing to coalesce concurrent
+    /// Scrapes
+    refreshes: Arc<tokio::sync::Mutex<Refreshes>>,
+    /// The Metrics of the Exporter
+    metrics: Vec<Box<dyn core::Collector>>,
+    /// The Metrics collected at the End of the last Refresh, which are returned when collecting
+    families: Arc<Mutex<Vec<MetricFamily>>>,
+}
+
+impl ScrapeCollector {
+    /// Creates a new Collector for the given Exporter, which reuses the loaded Metrics for at
+    /// least the given TTL
+    pub fn new(exporter: Arc<Exporter>, cache_ttl: Duration) -> Self {
+        let metrics = exporter.metric_collectors();
+
+        Self {
+            exporter,
+            cache_ttl,
+            refreshes: Arc::new(tokio::sync::Mutex::new(Refreshes::default())),
+            metrics,
+            families: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// The Exporter whose Collectors are run
+    pub fn exporter(&self) -> &Arc<Exporter> {
+        &self.exporter
+    }
+
+    /// Runs the enabled Collectors of the Exporter whose Interval has passed since their last
+    /// successful Run, unless the Metrics have been refreshed within the TTL. If a Refresh is
+    /// already running, this waits for it to finish instead of starting another one.
+    pub async fn refresh(&self) {
+        let mut refreshes = self.refreshes.lock().await;
+        if let Some(last) = refreshes.last {
+            if last.elapsed() < self.cache_ttl {
+                return;
+            }
+        }
+
+        let due: Vec<_> = self
+            .exporter
+            .schedule()
+            .iter()
+            .filter(
+                |(collector, interval)| match refreshes.collectors.get(collector) {
+                    Some(last) => last.elapsed() >= *interval,
+                    None => true,
+                },
+            )
+            .map(|(collector, _)| *collector)
+            .collect();
+
+        tracing::debug!(
+            "Refreshing {:?} for Account {:?}",
+            due,
+            self.exporter.client().name()
+        );
+        // Failed Collectors are retried on the next Refresh, but not before the TTL expires, to
+        // avoid hammering the API while it is having Problems
+        let runs = due.iter().map(|collector| self.exporter.run(*collector));
+        let results = future::join_all(runs).await;
+        let now = Instant::now();
+        for (collector, success) in due.into_iter().zip(results) {
+            if success {
+                refreshes.collectors.insert(collector, now);
+            }
+        }
+
+        let families = self.metrics.iter().flat_map(|m| m.collect()).collect();
+        *self.families.lock().unwrap() = families;
+        refreshes.last = Some(now);
+    }
+}
+
+impl Clone for ScrapeCollector {
+    fn clone(&self) -> Self {
+        Self {
+            exporter: self.exporter.clone(),
+            cache_ttl: self.cache_ttl,
+            refreshes: self.refreshes.clone(),
+            metrics: self.exporter.metric_collectors(),
+            families: self.families.clone(),
+        }
+    }
+}
+
+impl core::Collector for ScrapeCollector {
+    fn desc(&self) -> Vec<&Desc> {
+        self.metrics.iter().flat_map(|m| m.desc()).collect()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        self.families.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,201 @@
+//! Provides the loaded Resources as Targets for Prometheus' HTTP-Service-Discovery
+//! (`http_sd_config`)
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::RwLock,
+};
+
+use serde::Serialize;
+
+use crate::api::{Droplet, NetworkType};
+
+/// The Port that will be used for the Targets, if none is specified, which is the default Port
+/// of the node_exporter
+pub const DEFAULT_PORT: u16 = 9100;
+
+/// A single Group of Targets, that share the same Labels
+#[derive(Debug, Serialize)]
+pub struct TargetGroup {
+    /// The Addresses of all the Targets in this Group
+    pub targets: Vec<String>,
+    /// The Labels that will be attached to all the Targets
+    pub labels: BTreeMap<&'static str, String>,
+}
+
+/// The Options to control how the Targets will be generated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The Port that will be used for every Target
+    pub port: u16,
+    /// The Network from which the IP-Address for a Target will be used
+    pub network: NetworkType,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            port: DEFAULT_PORT,
+            network: NetworkType::Public,
+        }
+    }
+}
+
+impl Options {
+    /// Parses the Options from the Query-String of a Request, in the form of
+    /// `port=9100&network=private`
+    pub fn from_query(query: Option<&str>) -> Result<Self, String> {
+        let mut options = Self::default();
+
+        let pairs = query
+            .unwrap_or_default()
+            .split('&')
+            .filter(|p| !p.is_empty())
+            .map(|p| p.split_once('=').unwrap_or((p, "")));
+        for (key, value) in pairs {
+            match key {
+                "port" => {
+                    options.port = value
+                        .parse()
+                        .map_err(|_| format!("Invalid Port: {:?}", value))?;
+                }
+                "network" => {
+                    options.network = match value {
+                        "public" => NetworkType::Public,
+                        "private" => NetworkType::Private,
+                        other => return Err(format!("Invalid Network: {:?}", other)),
+                    };
+                }
+                other => return Err(format!("Unknown Parameter: {:?}", other)),
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// Holds the last loaded Droplets of all the Accounts, from which the Targets are generated
+#[derive(Debug, Default)]
+pub struct Discovery {
+    /// The Droplets by the Name of their Account
+    droplets: RwLock<HashMap<String, Vec<Droplet>>>,
+}
+
+impl Discovery {
+    /// Creates a new empty Discovery, that has not loaded any Droplets yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the Droplets of the given Account that are used for the Service-Discovery
+    pub(crate) fn update_droplets(&self, account: &str, droplets: Vec<Droplet>) {
+        self.droplets
+            .write()
+            .unwrap()
+            .insert(account.to_string(), droplets);
+    }
+
+    /// The Droplets of the given Account, if they have already been loaded
+    pub(crate) fn droplets(&self, account: &str) -> Option<Vec<Droplet>> {
+        self.droplets.read().unwrap().get(account).cloned()
+    }
+
+    /// Generates the Target-Groups for all the Droplets that have last been loaded, across all
+    /// Accounts.
+    ///
+    /// Returns None if the Droplets have not been loaded for any Account yet. Droplets without
+    /// an IPv4-Address in the requested Network are skipped.
+    pub fn droplet_targets(&self, options: Options) -> Option<Vec<TargetGroup>> {
+        let droplets = self.droplets.read().unwrap();
+        if droplets.is_empty() {
+            return None;
+        }
+
+        let groups = droplets
+            .iter()
+            .flat_map(|(account, droplets)| droplets.iter().map(move |d| (account, d)))
+            .filter_map(|(account, droplet)| {
+                let address = droplet.networks.ipv4(options.network)?;
+
+                let mut labels = BTreeMap::new();
+                labels.insert("__meta_digitalocean_account", account.clone());
+                labels.insert("__meta_digitalocean_droplet_id", droplet.id.to_string());
+                labels.insert("__meta_digitalocean_droplet_name", droplet.name.clone());
+                labels.insert("__meta_digitalocean_region", droplet.region.slug.clone());
+                labels.insert("__meta_digitalocean_size", droplet.size.slug.clone());
+                // Surrounded by Commas, to allow for simple Regex-Matching of a single Tag
+                labels.insert(
+                    "__meta_digitalocean_tags",
+                    format!(",{},", droplet.tags.join(",")),
+                );
+                if let Some(vpc) = droplet.vpc_uuid.as_ref() {
+                    labels.insert("__meta_digitalocean_vpc", vpc.clone());
+                }
+                if let Some(ip) = droplet.networks.ipv4(NetworkType::Public) {
+                    labels.insert("__meta_digitalocean_public_ipv4", ip.to_string());
+                }
+                if let Some(ip) = droplet.networks.ipv4(NetworkType::Private) {
+                    labels.insert("__meta_digitalocean_private_ipv4", ip.to_string());
+                }
+
+                Some(TargetGroup {
+                    targets: vec![format!("{}:{}", address, options.port)],
+                    labels,
+                })
+            })
+            .collect();
+
+        Some(groups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_without_query() {
+        assert_eq!(Options::from_query(None), Ok(Options::default()));
+        assert_eq!(Options::from_query(Some("")), Ok(Options::default()));
+    }
+
+    #[test]
+    fn parses_port_and_network() {
+        assert_eq!(
+            Options::from_query(Some("port=9256&network=private")),
+            Ok(Options {
+                port: 9256,
+                network: NetworkType::Private,
+            })
+        );
+        assert_eq!(
+            Options::from_query(Some("network=public&&port=1")),
+            Ok(Options {
+                port: 1,
+                network: NetworkType::Public,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_parameters() {
+        assert!(Options::from_query(Some("port=70000")).is_err());
+        assert!(Options::from_query(Some("port=")).is_err());
+        assert!(Options::from_query(Some("network=vpc")).is_err());
+        assert!(Options::from_query(Some("region=fra1")).is_err());
+    }
+
+    #[test]
+    fn no_targets_before_loading() {
+        let discovery = Discovery::new();
+        assert!(discovery.droplet_targets(Options::default()).is_none());
+
+        discovery.update_droplets("default", Vec::new());
+        assert_eq!(
+            discovery
+                .droplet_targets(Options::default())
+                .map(|t| t.len()),
+            Some(0)
+        );
+    }
+}
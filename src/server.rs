@@ -0,0 +1,408 @@
+//! The Webserver that exposes the Metrics, the Service-Discovery and the Health of the Exporter
+
+use std::{
+    fmt::{self, Display, Write},
+    net::SocketAddr,
+    sync::Arc,
+    time::Duration,
+};
+
+use hyper::{Body, Method, Request, Response, StatusCode};
+
+use crate::{scrape::ScrapeCollector, sd, Exporter};
+
+mod auth;
+mod exposition;
+mod openmetrics;
+mod tls;
+mod web_config;
+
+pub use auth::Auth;
+pub use exposition::Format;
+pub use openmetrics::{OpenMetricsEncoder, OPENMETRICS_FORMAT};
+pub use tls::{ReloadingConfig, TlsError};
+pub use web_config::{ClientAuthType, TlsServerConfig, WebConfig, WebConfigError};
+
+/// The Time after which a Connection is closed, if the Client has not completed the
+/// TLS-Handshake, so idle Connections can't pile up
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The Error returned when the Webserver can't be started or stops unexpectedly
+#[derive(Debug)]
+pub enum ServerError {
+    /// The Webserver could not listen on its Address
+    Bind(SocketAddr, Box<dyn std::error::Error + Send + Sync>),
+    /// The Webserver failed while serving
+    Serve(hyper::Error),
+}
+
+impl Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bind(addr, e) => write!(f, "Binding Webserver to {}: {}", addr, e),
+            Self::Serve(e) => write!(f, "Running Webserver: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ServerError {}
+
+/// The State shared by all the Requests to the Webserver
+pub struct State {
+    /// The Registry containing all the Metrics
+    pub registry: prometheus::Registry,
+    /// The Service-Discovery for the Droplets of all Accounts
+    pub discovery: Arc<sd::Discovery>,
+    /// The Exporters of all Accounts
+    pub exporters: Vec<Arc<Exporter>>,
+    /// The Collectors that load the Metrics on Scrape, empty if the Metrics are loaded
+    /// periodically instead
+    pub scrapers: Vec<ScrapeCollector>,
+    /// The Credentials required to access the Webserver
+    pub auth: Auth,
+}
+
+impl State {
+    /// Whether the Metrics of all Accounts have been loaded completely at least once. When
+    /// loading the Metrics on Scrape, there is nothing to wait for.
+    fn is_ready(&self) -> bool {
+        !self.scrapers.is_empty() || self.exporters.iter().all(|e| e.is_ready())
+    }
+}
+
+/// Creates a plain-text Response with the given Status
+fn text(status: StatusCode, body: &'static str) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(hyper::header::CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+async fn handle(state: Arc<State>, req: Request<Body>) -> Result<Response<Body>, String> {
+    if req.method() != Method::GET && req.method() != Method::HEAD {
+        return Ok(text(StatusCode::METHOD_NOT_ALLOWED, "Method Not Allowed\n"));
+    }
+
+    // The Health-Checks don't expose any Data and are usually probed by Orchestrators or
+    // Load-Balancers, which can't send any Credentials
+    match req.uri().path() {
+        "/healthz" => return Ok(text(StatusCode::OK, "ok\n")),
+        "/ready" if state.is_ready() => return Ok(text(StatusCode::OK, "ready\n")),
+        "/ready" => return Ok(text(StatusCode::SERVICE_UNAVAILABLE, "not ready\n")),
+        _ => {}
+    };
+
+    if !state.auth.is_authorized(&req).await {
+        let mut response = text(StatusCode::UNAUTHORIZED, "Unauthorized\n");
+        response.headers_mut().insert(
+            hyper::header::WWW_AUTHENTICATE,
+            hyper::header::HeaderValue::from_static("Basic realm=\"rustyocean\""),
+        );
+        return Ok(response);
+    }
+
+    let response = match req.uri().path() {
+        "/" => handle_landing_page(&state),
+        "/metrics" => handle_metrics(&state, &req).await,
+        "/sd/droplets" => {
+            refresh_scrapers(&state).await;
+            handle_sd_droplets(&state.discovery, &req)
+        }
+        _ => text(StatusCode::NOT_FOUND, "Not Found\n"),
+    };
+    Ok(response)
+}
+
+/// Refreshes the Metrics and Droplets of all the Accounts when loading them on Scrape, which is
+/// done concurrently instead of one Account after another
+async fn refresh_scrapers(state: &State) {
+    let refreshes: Vec<_> = state
+        .scrapers
+        .iter()
+        .cloned()
+        .map(|scraper| tokio::spawn(async move { scraper.refresh().await }))
+        .collect();
+    for refresh in refreshes {
+        if let Err(e) = refresh.await {
+            tracing::error!("Refreshing Metrics: {:?}", e);
+        }
+    }
+}
+
+async fn handle_metrics(state: &State, req: &Request<Body>) -> Response<Body> {
+    refresh_scrapers(state).await;
+
+    let header = |name| req.headers().get(name).and_then(|v| v.to_str().ok());
+    let format = Format::negotiate(header(hyper::header::ACCEPT));
+
+    let metrics = state.registry.gather();
+    let (mut body, content_type) = match format.encode(&metrics) {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::error!("Encoding Metrics as {:?}: {:?}", format, e);
+            return text(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Encoding Metrics failed\n",
+            );
+        }
+    };
+
+    let mut response = Response::builder()
+        .header(hyper::header::CONTENT_TYPE, content_type)
+        .header(hyper::header::VARY, "Accept, Accept-Encoding");
+    if exposition::accepts_gzip(header(hyper::header::ACCEPT_ENCODING)) {
+        match exposition::gzip(&body) {
+            Ok(compressed) => {
+                body = compressed;
+                response = response.header(hyper::header::CONTENT_ENCODING, "gzip");
+            }
+            Err(e) => tracing::error!("Compressing Metrics: {:?}", e),
+        }
+    }
+
+    response.body(Body::from(body)).unwrap()
+}
+
+fn handle_sd_droplets(discovery: &sd::Discovery, req: &Request<Body>) -> Response<Body> {
+    let options = match sd::Options::from_query(req.uri().query()) {
+        Ok(o) => o,
+        Err(e) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(e))
+                .unwrap();
+        }
+    };
+
+    let targets = match discovery.droplet_targets(options) {
+        Some(t) => t,
+        None => {
+            return text(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Droplets have not been loaded yet",
+            );
+        }
+    };
+
+    let body = serde_json::to_vec(&targets).unwrap();
+    Response::builder()
+        .header(hyper::header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+/// Escapes the given Text to be safely included in HTML
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Formats the given Time for the Landing-Page
+fn format_time(time: chrono::DateTime<chrono::Utc>) -> String {
+    time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// Renders a small Overview of all the Collectors and the Outcome of their last Run
+fn handle_landing_page(state: &State) -> Response<Body> {
+    let mut page = String::new();
+    page.push_str(concat!(
+        "<!DOCTYPE html>\n",
+        "<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Rustyocean</title>\n",
+        "<style>table { border-collapse: collapse; } ",
+        "th, td { border: 1px solid #ccc; padding: 0.25em 0.5em; text-align: left; } ",
+        ".failed { color: #b00; }</style>\n",
+        "</head>\n<body>\n<h1>Rustyocean</h1>\n",
+        "<p><a href=\"/metrics\">Metrics</a> | <a href=\"/sd/droplets\">Service-Discovery</a> | ",
+        "<a href=\"/healthz\">Health</a> | <a href=\"/ready\">Readiness</a></p>\n",
+    ));
+
+    for exporter in state.exporters.iter() {
+        let _ = write!(
+            page,
+            "<h2>Account {}</h2>\n<table>\n<tr><th>Collector</th><th>Interval</th>\
+             <th>Last Run</th><th>Duration</th><th>Last Success</th><th>Status</th></tr>\n",
+            escape_html(exporter.client().name())
+        );
+
+        let intervals = exporter.schedule();
+        for (collector, status) in exporter.status() {
+            let interval = intervals
+                .iter()
+                .find(|(c, _)| *c == collector)
+                .map(|(_, i)| humantime::format_duration(*i).to_string())
+                .unwrap_or_default();
+
+            let _ = write!(
+                page,
+                "<tr><td>{}</td><td>{}</td>",
+                collector.name(),
+                interval
+            );
+            match status {
+                None => page.push_str("<td>-</td><td>-</td><td>-</td><td>pending</td></tr>\n"),
+                Some(status) => {
+                    let last_success = status
+                        .last_success
+                        .map(format_time)
+                        .unwrap_or_else(|| "-".to_string());
+                    let result = match status.error.as_ref() {
+                        None => "ok".to_string(),
+                        Some(e) => format!("<span class=\"failed\">{}</span>", escape_html(e)),
+                    };
+
+                    let _ = writeln!(
+                        page,
+                        "<td>{}</td><td>{:.3}s</td><td>{}</td><td>{}</td></tr>",
+                        format_time(status.last_run),
+                        status.duration.as_secs_f64(),
+                        last_success,
+                        result
+                    );
+                }
+            }
+        }
+        page.push_str("</table>\n");
+    }
+    page.push_str("</body>\n</html>\n");
+
+    Response::builder()
+        .header(hyper::header::CONTENT_TYPE, "text/html; charset=utf-8")
+        .body(Body::from(page))
+        .unwrap()
+}
+
+/// Serves the Webserver on the given Address until it fails, using TLS if a Configuration
+/// is given. Returns an Error if the Address can't be bound or serving fails.
+pub async fn run(
+    addr: SocketAddr,
+    state: State,
+    tls: Option<Arc<ReloadingConfig>>,
+) -> Result<(), ServerError> {
+    let state = Arc::new(state);
+    match tls {
+        Some(tls) => run_tls(addr, state, tls).await,
+        None => run_plain(addr, state).await,
+    }
+}
+
+async fn run_plain(addr: SocketAddr, state: Arc<State>) -> Result<(), ServerError> {
+    let make_service = hyper::service::make_service_fn(move |_conn| {
+        let state = state.clone();
+        async move {
+            Ok::<_, String>(hyper::service::service_fn(move |req| {
+                handle(state.clone(), req)
+            }))
+        }
+    });
+
+    hyper::Server::try_bind(&addr)
+        .map_err(|e| ServerError::Bind(addr, e.into()))?
+        .serve(make_service)
+        .await
+        .map_err(ServerError::Serve)
+}
+
+async fn run_tls(
+    addr: SocketAddr,
+    state: Arc<State>,
+    tls: Arc<ReloadingConfig>,
+) -> Result<(), ServerError> {
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| ServerError::Bind(addr, e.into()))?;
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(c) => c,
+            Err(e) => {
+                tracing::error!("Accepting Connection: {:?}", e);
+                continue;
+            }
+        };
+
+        // Every Connection uses the latest Configuration, so reloaded Certificates are picked
+        // up without restarting the Server
+        let acceptor = tokio_rustls::TlsAcceptor::from(tls.current());
+        let state = state.clone();
+        tokio::spawn(async move {
+            let handshake = tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream));
+            let stream = match handshake.await {
+                Ok(Ok(s)) => s,
+                Ok(Err(e)) => {
+                    tracing::debug!("TLS-Handshake with {}: {:?}", peer, e);
+                    return;
+                }
+                Err(_) => {
+                    tracing::debug!("TLS-Handshake with {} timed out", peer);
+                    return;
+                }
+            };
+
+            let service = hyper::service::service_fn(move |req| handle(state.clone(), req));
+            if let Err(e) = hyper::server::conn::Http::new()
+                .serve_connection(stream, service)
+                .await
+            {
+                tracing::debug!("Serving Connection from {}: {:?}", peer, e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> Arc<State> {
+        let mut config = WebConfig::default();
+        config.bearer_tokens.push("token".to_string());
+
+        Arc::new(State {
+            registry: prometheus::Registry::new(),
+            discovery: Arc::new(sd::Discovery::new()),
+            exporters: Vec::new(),
+            scrapers: Vec::new(),
+            auth: Auth::new(&config),
+        })
+    }
+
+    async fn status(path: &str, token: Option<&str>) -> StatusCode {
+        let mut req = Request::builder().uri(path);
+        if let Some(token) = token {
+            req = req.header(hyper::header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        let response = handle(state(), req.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        response.status()
+    }
+
+    #[tokio::test]
+    async fn health_checks_need_no_credentials() {
+        assert_eq!(status("/healthz", None).await, StatusCode::OK);
+        assert_eq!(status("/ready", None).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn protects_everything_else() {
+        for path in ["/", "/metrics", "/sd/droplets", "/unknown"] {
+            assert_eq!(status(path, None).await, StatusCode::UNAUTHORIZED);
+        }
+        assert_eq!(status("/metrics", Some("token")).await, StatusCode::OK);
+        assert_eq!(
+            status("/unknown", Some("token")).await,
+            StatusCode::NOT_FOUND
+        );
+    }
+}
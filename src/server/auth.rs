@@ -0,0 +1,216 @@
+//! Protects the Webserver using Basic-Auth or Bearer-Tokens
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use hyper::{header, Body, Request};
+use ring::digest;
+use tokio::sync::Semaphore;
+
+use super::web_config::WebConfig;
+
+/// The Number of Passwords that are verified using bcrypt at the same Time
+const MAX_CONCURRENT_VERIFICATIONS: usize = 2;
+
+/// The Credentials that are allowed to access the Webserver
+#[derive(Debug, Clone)]
+pub struct Auth {
+    /// The bcrypt-Hashes of the Passwords by their User
+    basic_auth_users: Arc<BTreeMap<String, String>>,
+    /// The allowed Bearer-Tokens
+    bearer_tokens: Arc<Vec<String>>,
+    /// The SHA-256 Digests of the Basic-Auth Credentials that have already been verified, so
+    /// bcrypt only has to run once for every User instead of on every Scrape
+    verified: Arc<Mutex<HashSet<Vec<u8>>>>,
+    /// Limits the Number of Passwords that are verified at the same Time, so invalid
+    /// Credentials can not use up all the CPU
+    permits: Arc<Semaphore>,
+}
+
+impl Default for Auth {
+    fn default() -> Self {
+        Self::new(&WebConfig::default())
+    }
+}
+
+impl Auth {
+    /// Creates the Authentication from the given Web-Configuration, which allows every Request
+    /// if neither Users nor Tokens are configured
+    pub fn new(config: &WebConfig) -> Self {
+        Self {
+            basic_auth_users: Arc::new(config.basic_auth_users.clone()),
+            bearer_tokens: Arc::new(config.bearer_tokens.clone()),
+            verified: Arc::new(Mutex::new(HashSet::new())),
+            permits: Arc::new(Semaphore::new(MAX_CONCURRENT_VERIFICATIONS)),
+        }
+    }
+
+    /// Whether or not any Credentials are required at all
+    pub fn is_enabled(&self) -> bool {
+        !self.basic_auth_users.is_empty() || !self.bearer_tokens.is_empty()
+    }
+
+    /// Whether or not the given Request carries valid Credentials
+    pub async fn is_authorized(&self, req: &Request<Body>) -> bool {
+        if !self.is_enabled() {
+            return true;
+        }
+
+        let value = match req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+        {
+            Some(v) => v,
+            None => return false,
+        };
+
+        if let Some(token) = value.strip_prefix("Bearer ") {
+            let token = token.trim().as_bytes();
+            // Every Token is compared, to not leak which one matched through the Timing
+            return self.bearer_tokens.iter().fold(false, |found, t| {
+                constant_time_eq(t.as_bytes(), token) | found
+            });
+        }
+        if let Some(encoded) = value.strip_prefix("Basic ") {
+            return self.check_basic_auth(encoded.trim()).await;
+        }
+        false
+    }
+
+    /// Checks the base64-encoded `user:password` Pair against the configured Users
+    async fn check_basic_auth(&self, encoded: &str) -> bool {
+        let decoded = match base64::decode(encoded)
+            .ok()
+            .and_then(|d| String::from_utf8(d).ok())
+        {
+            Some(d) => d,
+            None => return false,
+        };
+        let (user, password) = match decoded.split_once(':') {
+            Some(pair) => pair,
+            None => return false,
+        };
+        let hash = match self.basic_auth_users.get(user) {
+            Some(h) => h.clone(),
+            None => return false,
+        };
+
+        // The Digest also covers the Hash, so changed Credentials are never matched
+        let mut context = digest::Context::new(&digest::SHA256);
+        for part in [user, "\0", &hash, "\0", password] {
+            context.update(part.as_bytes());
+        }
+        let credentials = context.finish().as_ref().to_vec();
+        if self.verified.lock().unwrap().contains(&credentials) {
+            return true;
+        }
+
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .expect("The Semaphore is never closed");
+
+        // bcrypt is deliberately slow, so it should not block the other Requests
+        let password = password.to_string();
+        match tokio::task::spawn_blocking(move || bcrypt::verify(password, &hash)).await {
+            Ok(Ok(true)) => {
+                self.verified.lock().unwrap().insert(credentials);
+                true
+            }
+            Ok(Ok(false)) => false,
+            Ok(Err(e)) => {
+                tracing::error!("Verifying Password of User {:?}: {:?}", user, e);
+                false
+            }
+            Err(e) => {
+                tracing::error!("Verifying Password of User {:?}: {:?}", user, e);
+                false
+            }
+        }
+    }
+}
+
+/// Compares the two Values in a Time that only depends on their Length
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(authorization: Option<&str>) -> Request<Body> {
+        let mut req = Request::builder().uri("/metrics");
+        if let Some(value) = authorization {
+            req = req.header(header::AUTHORIZATION, value);
+        }
+        req.body(Body::empty()).unwrap()
+    }
+
+    fn basic(credentials: &str) -> String {
+        format!("Basic {}", base64::encode(credentials))
+    }
+
+    fn auth() -> Auth {
+        let mut config = WebConfig::default();
+        config
+            .basic_auth_users
+            .insert("prometheus".to_string(), bcrypt::hash("secret", 4).unwrap());
+        config.bearer_tokens.push("token".to_string());
+        Auth::new(&config)
+    }
+
+    #[tokio::test]
+    async fn allows_everything_without_credentials_configured() {
+        assert!(Auth::default().is_authorized(&request(None)).await);
+    }
+
+    #[tokio::test]
+    async fn checks_basic_auth() {
+        let auth = auth();
+
+        assert!(!auth.is_authorized(&request(None)).await);
+        assert!(
+            !auth
+                .is_authorized(&request(Some(&basic("prometheus:wrong"))))
+                .await
+        );
+        assert!(
+            !auth
+                .is_authorized(&request(Some(&basic("other:secret"))))
+                .await
+        );
+        assert!(auth.verified.lock().unwrap().is_empty());
+
+        assert!(
+            auth.is_authorized(&request(Some(&basic("prometheus:secret"))))
+                .await
+        );
+        assert_eq!(auth.verified.lock().unwrap().len(), 1);
+
+        // The Clones share the verified Credentials
+        assert!(
+            auth.clone()
+                .is_authorized(&request(Some(&basic("prometheus:secret"))))
+                .await
+        );
+        assert_eq!(auth.verified.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn checks_bearer_tokens() {
+        let auth = auth();
+
+        assert!(auth.is_authorized(&request(Some("Bearer token"))).await);
+        assert!(!auth.is_authorized(&request(Some("Bearer other"))).await);
+        assert!(!auth.is_authorized(&request(Some("Bearer "))).await);
+        assert!(!auth.is_authorized(&request(Some("token"))).await);
+    }
+}
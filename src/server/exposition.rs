@@ -0,0 +1,252 @@
+//! Chooses the Format and Compression of the Metrics based on the Headers of the Scrape
+
+use std::io::Write;
+
+use prometheus::{proto::MetricFamily, Encoder};
+
+use super::openmetrics::OpenMetricsEncoder;
+
+/// The Formats in which the Metrics can be exposed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// The classic Prometheus Text-Format (version 0.0.4)
+    Text,
+    /// The OpenMetrics Text-Format (version 1.0.0)
+    OpenMetrics,
+    /// The length-delimited Protobuf-Format
+    Protobuf,
+}
+
+/// A single Entry of an `Accept` or `Accept-Encoding` Header, with its Parameters
+struct Entry<'a> {
+    /// The Media-Type or Content-Coding, in lowercase
+    value: String,
+    /// All the Parameters except for the Quality
+    params: Vec<(String, &'a str)>,
+    /// The Quality, between 0 and 1
+    quality: f32,
+}
+
+impl Entry<'_> {
+    /// Returns the Value of the given Parameter, if it is set
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.trim_matches('"'))
+    }
+}
+
+/// Parses a comma-separated Header like `Accept` into its Entries, skipping the invalid ones
+fn parse_header(header: &str) -> Vec<Entry<'_>> {
+    header
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';').map(str::trim);
+            let value = parts.next().filter(|v| !v.is_empty())?.to_ascii_lowercase();
+
+            let mut quality = 1.0;
+            let mut params = Vec::new();
+            for param in parts {
+                let (name, v) = param.split_once('=')?;
+                let name = name.trim().to_ascii_lowercase();
+                if name == "q" {
+                    quality = v.trim().parse().ok()?;
+                } else {
+                    params.push((name, v.trim()));
+                }
+            }
+
+            Some(Entry {
+                value,
+                params,
+                quality,
+            })
+        })
+        .collect()
+}
+
+impl Format {
+    /// Chooses the Format that the Client prefers, based on its `Accept` Header. Falls back to
+    /// the Text-Format if none of the accepted Formats are supported.
+    pub fn negotiate(accept: Option<&str>) -> Self {
+        let mut best = (Self::Text, 0.0);
+
+        for entry in parse_header(accept.unwrap_or_default()) {
+            let format = match entry.value.as_str() {
+                "application/vnd.google.protobuf"
+                    if entry.param("proto") == Some("io.prometheus.client.MetricFamily")
+                        && entry.param("encoding") == Some("delimited") =>
+                {
+                    Self::Protobuf
+                }
+                "application/openmetrics-text"
+                    if matches!(entry.param("version"), None | Some("1.0.0") | Some("0.0.1")) =>
+                {
+                    Self::OpenMetrics
+                }
+                "text/plain" if matches!(entry.param("version"), None | Some("0.0.4")) => {
+                    Self::Text
+                }
+                "text/*" | "*/*" => Self::Text,
+                _ => continue,
+            };
+
+            // On equal Quality the first Format listed by the Client wins
+            if entry.quality > best.1 {
+                best = (format, entry.quality);
+            }
+        }
+
+        best.0
+    }
+
+    /// Encodes the Metrics in this Format, returning them together with their Content-Type
+    pub fn encode(self, families: &[MetricFamily]) -> prometheus::Result<(Vec<u8>, String)> {
+        fn encode<E: Encoder>(
+            encoder: E,
+            families: &[MetricFamily],
+        ) -> prometheus::Result<(Vec<u8>, String)> {
+            let mut buffer = Vec::new();
+            encoder.encode(families, &mut buffer)?;
+            Ok((buffer, encoder.format_type().to_string()))
+        }
+
+        match self {
+            Self::Text => encode(prometheus::TextEncoder::new(), families),
+            Self::OpenMetrics => encode(OpenMetricsEncoder::new(), families),
+            Self::Protobuf => encode(prometheus::ProtobufEncoder::new(), families),
+        }
+    }
+}
+
+/// Whether the Client accepts gzip-compressed Responses, based on its `Accept-Encoding` Header
+pub fn accepts_gzip(accept_encoding: Option<&str>) -> bool {
+    let entries = parse_header(accept_encoding.unwrap_or_default());
+
+    // An explicit Entry for gzip takes precedence over the Wildcard
+    let gzip = entries.iter().find(|e| e.value == "gzip");
+    let wildcard = entries.iter().find(|e| e.value == "*");
+    gzip.or(wildcard).map(|e| e.quality > 0.0).unwrap_or(false)
+}
+
+/// Compresses the given Data using gzip
+pub fn gzip(data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiates_prometheus_defaults() {
+        // Sent by Prometheus 2.x with OpenMetrics enabled (the Default)
+        let accept = "application/openmetrics-text;version=1.0.0,application/openmetrics-text;\
+                      version=0.0.1;q=0.75,text/plain;version=0.0.4;q=0.5,*/*;q=0.1";
+        assert_eq!(Format::negotiate(Some(accept)), Format::OpenMetrics);
+
+        // Sent by Prometheus with the Protobuf-Format enabled
+        let accept = "application/vnd.google.protobuf;proto=io.prometheus.client.MetricFamily;\
+                      encoding=delimited;q=0.7,text/plain;version=0.0.4;q=0.3,*/*;q=0.1";
+        assert_eq!(Format::negotiate(Some(accept)), Format::Protobuf);
+
+        // Sent by Prometheus with OpenMetrics disabled
+        let accept = "text/plain;version=0.0.4;q=1,*/*;q=0.1";
+        assert_eq!(Format::negotiate(Some(accept)), Format::Text);
+    }
+
+    #[test]
+    fn falls_back_to_text() {
+        assert_eq!(Format::negotiate(None), Format::Text);
+        assert_eq!(Format::negotiate(Some("")), Format::Text);
+        assert_eq!(Format::negotiate(Some("application/json")), Format::Text);
+        assert_eq!(
+            Format::negotiate(Some("application/openmetrics-text;version=2.0.0")),
+            Format::Text
+        );
+        // The Protobuf-Format is only used with the expected Parameters
+        assert_eq!(
+            Format::negotiate(Some("application/vnd.google.protobuf")),
+            Format::Text
+        );
+    }
+
+    #[test]
+    fn skips_rejected_formats() {
+        assert_eq!(
+            Format::negotiate(Some("application/openmetrics-text;q=0")),
+            Format::Text
+        );
+        assert_eq!(
+            Format::negotiate(Some("application/openmetrics-text;q=0,text/plain;q=0.1")),
+            Format::Text
+        );
+        assert_eq!(
+            Format::negotiate(Some("text/plain;q=0.5,application/openmetrics-text;q=0.9")),
+            Format::OpenMetrics
+        );
+    }
+
+    #[test]
+    fn prefers_first_on_equal_quality() {
+        assert_eq!(
+            Format::negotiate(Some("application/openmetrics-text, text/plain")),
+            Format::OpenMetrics
+        );
+        assert_eq!(
+            Format::negotiate(Some("text/plain, application/openmetrics-text")),
+            Format::Text
+        );
+    }
+
+    #[test]
+    fn parses_headers_leniently() {
+        let entries = parse_header(" Text/Plain ; Version=\"0.0.4\" ; q=0.5 ,, invalid;q=x, gzip");
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].value, "text/plain");
+        assert_eq!(entries[0].param("version"), Some("0.0.4"));
+        assert_eq!(entries[0].quality, 0.5);
+        assert_eq!(entries[1].value, "gzip");
+        assert_eq!(entries[1].quality, 1.0);
+    }
+
+    #[test]
+    fn checks_gzip() {
+        assert!(!accepts_gzip(None));
+        assert!(!accepts_gzip(Some("identity")));
+        assert!(accepts_gzip(Some("gzip, deflate")));
+        assert!(accepts_gzip(Some("*")));
+        assert!(!accepts_gzip(Some("gzip;q=0")));
+        assert!(!accepts_gzip(Some("gzip;q=0, *")));
+        assert!(accepts_gzip(Some("deflate, gzip;q=0.5, *;q=0")));
+    }
+
+    #[test]
+    fn compresses_with_gzip() {
+        use std::io::Read;
+
+        let data = b"metric 1\n".repeat(100);
+        let compressed = gzip(&data).unwrap();
+        assert!(compressed.len() < data.len());
+
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(compressed.as_slice())
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, data);
+    }
+
+    #[test]
+    fn encodes_with_content_type() {
+        let (body, content_type) = Format::OpenMetrics.encode(&[]).unwrap();
+        assert_eq!(body, b"# EOF\n");
+        assert_eq!(content_type, crate::server::OPENMETRICS_FORMAT);
+
+        let (_, content_type) = Format::Text.encode(&[]).unwrap();
+        assert!(content_type.starts_with("text/plain; version=0.0.4"));
+    }
+}
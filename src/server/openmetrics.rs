@@ -0,0 +1,398 @@
+//! Encodes the Metrics in the OpenMetrics Text-Format, which is not supported by the
+//! prometheus Crate itself
+
+use std::io::Write;
+
+use prometheus::{
+    proto::{LabelPair, Metric, MetricFamily, MetricType},
+    Encoder,
+};
+
+/// The Content-Type of the OpenMetrics Text-Format
+pub const OPENMETRICS_FORMAT: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// The Units that are recognized from the Suffix of a Metric-Name, these are the Base-Units
+/// recommended by Prometheus
+const UNITS: &[&str] = &[
+    "seconds", "bytes", "ratio", "celsius", "meters", "grams", "joules", "volts", "amperes",
+];
+
+/// An Encoder for the OpenMetrics Text-Format.
+///
+/// Counters are exposed with the `_total` Suffix, the Unit of a Metric is derived from the
+/// Suffix of its Name and Gauges named `*_info` are exposed as Info-Metrics, as long as all their
+/// Samples have the Value 1.
+#[derive(Debug, Default)]
+pub struct OpenMetricsEncoder;
+
+impl OpenMetricsEncoder {
+    /// Creates a new Encoder
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Encoder for OpenMetricsEncoder {
+    fn encode<W: Write>(
+        &self,
+        families: &[MetricFamily],
+        writer: &mut W,
+    ) -> prometheus::Result<()> {
+        for family in families {
+            if family.get_name().is_empty() || family.get_metric().is_empty() {
+                return Err(prometheus::Error::Msg(format!(
+                    "Invalid MetricFamily: {:?}",
+                    family
+                )));
+            }
+            encode_family(family, writer)?;
+        }
+        writer.write_all(b"# EOF\n")?;
+        Ok(())
+    }
+
+    fn format_type(&self) -> &str {
+        OPENMETRICS_FORMAT
+    }
+}
+
+/// The Type of a Metric-Family in the OpenMetrics Format
+fn family_type(family: &MetricFamily) -> &'static str {
+    match family.get_field_type() {
+        MetricType::COUNTER => "counter",
+        // Info-Samples must have the Value 1, otherwise they are exposed as a regular Gauge
+        MetricType::GAUGE
+            if family.get_name().ends_with("_info")
+                && family
+                    .get_metric()
+                    .iter()
+                    .all(|m| m.get_gauge().get_value() == 1.0) =>
+        {
+            "info"
+        }
+        MetricType::GAUGE => "gauge",
+        MetricType::HISTOGRAM => "histogram",
+        MetricType::SUMMARY => "summary",
+        MetricType::UNTYPED => "unknown",
+    }
+}
+
+fn encode_family<W: Write>(family: &MetricFamily, writer: &mut W) -> prometheus::Result<()> {
+    let kind = family_type(family);
+
+    // The Name of the Family excludes the Suffix that is added to its Samples
+    let name = family.get_name();
+    let (name, suffix) = match kind {
+        "counter" => (name.strip_suffix("_total").unwrap_or(name), "_total"),
+        "info" => (name.strip_suffix("_info").unwrap_or(name), "_info"),
+        _ => (name, ""),
+    };
+
+    writeln!(writer, "# TYPE {} {}", name, kind)?;
+    if let Some(unit) = UNITS.iter().find(|u| name.ends_with(&format!("_{}", u))) {
+        writeln!(writer, "# UNIT {} {}", name, unit)?;
+    }
+    if !family.get_help().is_empty() {
+        writeln!(writer, "# HELP {} {}", name, escape(family.get_help()))?;
+    }
+
+    for metric in family.get_metric() {
+        match family.get_field_type() {
+            MetricType::COUNTER => {
+                let value = metric.get_counter().get_value();
+                write_sample(writer, name, suffix, metric, None, value)?;
+            }
+            MetricType::GAUGE => {
+                let value = metric.get_gauge().get_value();
+                write_sample(writer, name, suffix, metric, None, value)?;
+            }
+            MetricType::UNTYPED => {
+                let value = metric.get_untyped().get_value();
+                write_sample(writer, name, suffix, metric, None, value)?;
+            }
+            MetricType::HISTOGRAM => {
+                let histogram = metric.get_histogram();
+                let mut has_inf = false;
+                for bucket in histogram.get_bucket() {
+                    has_inf |= bucket.get_upper_bound().is_infinite();
+                    let le = format_bound(bucket.get_upper_bound());
+                    write_sample(
+                        writer,
+                        name,
+                        "_bucket",
+                        metric,
+                        Some(("le", &le)),
+                        bucket.get_cumulative_count() as f64,
+                    )?;
+                }
+                // The +Inf Bucket is required, but not included by the prometheus Crate
+                if !has_inf {
+                    write_sample(
+                        writer,
+                        name,
+                        "_bucket",
+                        metric,
+                        Some(("le", "+Inf")),
+                        histogram.get_sample_count() as f64,
+                    )?;
+                }
+                write_sample(
+                    writer,
+                    name,
+                    "_sum",
+                    metric,
+                    None,
+                    histogram.get_sample_sum(),
+                )?;
+                write_sample(
+                    writer,
+                    name,
+                    "_count",
+                    metric,
+                    None,
+                    histogram.get_sample_count() as f64,
+                )?;
+            }
+            MetricType::SUMMARY => {
+                let summary = metric.get_summary();
+                for quantile in summary.get_quantile() {
+                    let q = format_bound(quantile.get_quantile());
+                    write_sample(
+                        writer,
+                        name,
+                        "",
+                        metric,
+                        Some(("quantile", &q)),
+                        quantile.get_value(),
+                    )?;
+                }
+                write_sample(writer, name, "_sum", metric, None, summary.get_sample_sum())?;
+                write_sample(
+                    writer,
+                    name,
+                    "_count",
+                    metric,
+                    None,
+                    summary.get_sample_count() as f64,
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a single Sample, with an optional additional Label like the `le` of a Bucket
+fn write_sample<W: Write>(
+    writer: &mut W,
+    name: &str,
+    suffix: &str,
+    metric: &Metric,
+    extra_label: Option<(&str, &str)>,
+    value: f64,
+) -> prometheus::Result<()> {
+    write!(writer, "{}{}", name, suffix)?;
+    write_labels(writer, metric.get_label(), extra_label)?;
+    write!(writer, " {}", format_float(value))?;
+    // OpenMetrics uses Seconds instead of Milliseconds for the Timestamps
+    if metric.get_timestamp_ms() != 0 {
+        write!(
+            writer,
+            " {}",
+            format_float(metric.get_timestamp_ms() as f64 / 1000.0)
+        )?;
+    }
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+fn write_labels<W: Write>(
+    writer: &mut W,
+    labels: &[LabelPair],
+    extra_label: Option<(&str, &str)>,
+) -> prometheus::Result<()> {
+    let mut pairs = labels
+        .iter()
+        .map(|l| (l.get_name(), l.get_value()))
+        .chain(extra_label)
+        .peekable();
+    if pairs.peek().is_none() {
+        return Ok(());
+    }
+
+    writer.write_all(b"{")?;
+    for (index, (name, value)) in pairs.enumerate() {
+        if index > 0 {
+            writer.write_all(b",")?;
+        }
+        write!(writer, "{}=\"{}\"", name, escape(value))?;
+    }
+    writer.write_all(b"}")?;
+    Ok(())
+}
+
+/// Formats the Value as required by OpenMetrics, which differs from Rust for the special Values
+fn format_float(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// Formats the `le` or `quantile` Label-Value, which always has to be written as a Float
+fn format_bound(value: f64) -> String {
+    if value.is_finite() {
+        format!("{:?}", value)
+    } else {
+        format_float(value)
+    }
+}
+
+/// Escapes the Backslashes, Newlines and double Quotes in a Label-Value or Help-Text
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '"' => escaped.push_str("\\\""),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use prometheus::{
+        Counter, Gauge, GaugeVec, Histogram, HistogramOpts, IntCounter, Opts, Registry,
+    };
+
+    use super::*;
+
+    fn encode(registry: &Registry) -> String {
+        let mut buffer = Vec::new();
+        OpenMetricsEncoder::new()
+            .encode(&registry.gather(), &mut buffer)
+            .unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn ends_with_eof() {
+        let registry = Registry::new();
+        assert_eq!(encode(&registry), "# EOF\n");
+
+        let gauge = Gauge::new("temperature_celsius", "The Temperature").unwrap();
+        registry.register(Box::new(gauge.clone())).unwrap();
+        gauge.set(21.5);
+
+        assert_eq!(
+            encode(&registry),
+            "# TYPE temperature_celsius gauge\n\
+             # UNIT temperature_celsius celsius\n\
+             # HELP temperature_celsius The Temperature\n\
+             temperature_celsius 21.5\n\
+             # EOF\n"
+        );
+    }
+
+    #[test]
+    fn suffixes_counters() {
+        let registry = Registry::new();
+        let requests = IntCounter::new("requests_total", "The Requests").unwrap();
+        let errors = Counter::new("errors", "The Errors").unwrap();
+        registry.register(Box::new(requests.clone())).unwrap();
+        registry.register(Box::new(errors.clone())).unwrap();
+        requests.inc_by(3);
+
+        assert_eq!(
+            encode(&registry),
+            "# TYPE errors counter\n\
+             # HELP errors The Errors\n\
+             errors_total 0\n\
+             # TYPE requests counter\n\
+             # HELP requests The Requests\n\
+             requests_total 3\n\
+             # EOF\n"
+        );
+    }
+
+    #[test]
+    fn info_type_requires_value_one() {
+        let registry = Registry::new();
+        let build = GaugeVec::new(Opts::new("build_info", "The Build"), &["version"]).unwrap();
+        registry.register(Box::new(build.clone())).unwrap();
+
+        build.with_label_values(&["1.0"]).set(1.0);
+        assert!(encode(&registry).starts_with("# TYPE build info\n"));
+
+        // Info-Samples must have the Value 1, so the Family falls back to a Gauge
+        build.with_label_values(&["2.0"]).set(0.0);
+        let encoded = encode(&registry);
+        assert!(encoded.starts_with("# TYPE build_info gauge\n"));
+        assert!(encoded.contains("build_info{version=\"2.0\"} 0\n"));
+    }
+
+    #[test]
+    fn adds_inf_bucket() {
+        let registry = Registry::new();
+        let histogram = Histogram::with_opts(
+            HistogramOpts::new("duration_seconds", "The Duration").buckets(vec![0.5, 1.0]),
+        )
+        .unwrap();
+        registry.register(Box::new(histogram.clone())).unwrap();
+        histogram.observe(0.25);
+        histogram.observe(2.0);
+
+        assert_eq!(
+            encode(&registry),
+            "# TYPE duration_seconds histogram\n\
+             # UNIT duration_seconds seconds\n\
+             # HELP duration_seconds The Duration\n\
+             duration_seconds_bucket{le=\"0.5\"} 1\n\
+             duration_seconds_bucket{le=\"1.0\"} 1\n\
+             duration_seconds_bucket{le=\"+Inf\"} 2\n\
+             duration_seconds_sum 2.25\n\
+             duration_seconds_count 2\n\
+             # EOF\n"
+        );
+    }
+
+    #[test]
+    fn escapes_labels_and_help() {
+        let registry = Registry::new();
+        let gauge = GaugeVec::new(
+            Opts::new("escaped", "Help with \\ and\nnewline"),
+            &["value"],
+        )
+        .unwrap();
+        registry.register(Box::new(gauge.clone())).unwrap();
+        gauge
+            .with_label_values(&["quote \" backslash \\ newline \n"])
+            .set(1.0);
+
+        assert_eq!(
+            encode(&registry),
+            "# TYPE escaped gauge\n\
+             # HELP escaped Help with \\\\ and\\nnewline\n\
+             escaped{value=\"quote \\\" backslash \\\\ newline \\n\"} 1\n\
+             # EOF\n"
+        );
+    }
+
+    #[test]
+    fn formats_special_values() {
+        assert_eq!(format_float(f64::NAN), "NaN");
+        assert_eq!(format_float(f64::INFINITY), "+Inf");
+        assert_eq!(format_float(f64::NEG_INFINITY), "-Inf");
+        assert_eq!(format_float(1.5), "1.5");
+        assert_eq!(format_bound(1.0), "1.0");
+        assert_eq!(format_bound(f64::INFINITY), "+Inf");
+    }
+}
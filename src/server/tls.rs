@@ -0,0 +1,232 @@
+//! Serves the Webserver over TLS, reloading the Certificate whenever its Files change
+
+use std::{
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use tokio_rustls::rustls;
+
+use super::web_config::{ClientAuthType, TlsServerConfig};
+
+/// The Error returned when the TLS-Configuration could not be loaded from its Files
+#[derive(Debug)]
+pub enum TlsError {
+    /// One of the Files could not be read
+    Read(PathBuf, std::io::Error),
+    /// The File did not contain any Certificates
+    NoCertificates(PathBuf),
+    /// The File did not contain a private Key
+    NoPrivateKey(PathBuf),
+    /// A Certificate from the Client-CA-File could not be used
+    InvalidClientCa(PathBuf),
+    /// Client-Certificates should be verified, but no Client-CA-File is configured
+    MissingClientCa(ClientAuthType),
+    /// The Certificate and Key could not be used together
+    Rustls(rustls::Error),
+}
+
+impl Display for TlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(path, e) => write!(f, "Reading {:?}: {}", path, e),
+            Self::NoCertificates(path) => write!(f, "No Certificates found in {:?}", path),
+            Self::NoPrivateKey(path) => write!(f, "No private Key found in {:?}", path),
+            Self::InvalidClientCa(path) => write!(f, "Invalid CA-Certificate in {:?}", path),
+            Self::MissingClientCa(auth_type) => {
+                write!(f, "A Client-CA-File is required for {:?}", auth_type)
+            }
+            Self::Rustls(e) => write!(f, "Configuring TLS: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for TlsError {}
+
+/// Holds the current TLS-Configuration of the Server, which is used for every new Connection
+pub struct ReloadingConfig {
+    /// The Files from which the TLS-Configuration is loaded
+    files: TlsServerConfig,
+    /// The Content of the Files from which the current TLS-Configuration was loaded
+    loaded_from: RwLock<Vec<Vec<u8>>>,
+    /// The TLS-Configuration for new Connections
+    current: RwLock<Arc<rustls::ServerConfig>>,
+}
+
+impl ReloadingConfig {
+    /// Loads the initial TLS-Configuration from the given Files
+    pub fn load(files: TlsServerConfig) -> Result<Self, TlsError> {
+        let content = read_files(&files)?;
+        let config = build_server_config(&files, &content)?;
+
+        Ok(Self {
+            files,
+            loaded_from: RwLock::new(content),
+            current: RwLock::new(Arc::new(config)),
+        })
+    }
+
+    /// The TLS-Configuration that should be used for a new Connection
+    pub fn current(&self) -> Arc<rustls::ServerConfig> {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Reloads the TLS-Configuration, if any of its Files changed since it was last loaded.
+    ///
+    /// Returns whether the TLS-Configuration has been replaced.
+    pub fn reload(&self) -> Result<bool, TlsError> {
+        let content = read_files(&self.files)?;
+        if *self.loaded_from.read().unwrap() == content {
+            return Ok(false);
+        }
+
+        let config = build_server_config(&self.files, &content)?;
+        *self.current.write().unwrap() = Arc::new(config);
+        *self.loaded_from.write().unwrap() = content;
+        Ok(true)
+    }
+
+    /// This will run forever and periodically check the Files for Changes, replacing the
+    /// TLS-Configuration whenever they changed.
+    ///
+    /// If the new Files can not be loaded, the previous TLS-Configuration is kept, as the
+    /// Certificate and Key are usually not replaced at exactly the same Time.
+    pub async fn watch(self: Arc<Self>, interval: Duration) {
+        loop {
+            tokio::time::sleep(interval).await;
+
+            match self.reload() {
+                Ok(true) => tracing::info!("Reloaded the TLS-Certificate"),
+                Ok(false) => {}
+                Err(e) => tracing::error!("Reloading the TLS-Certificate: {}", e),
+            }
+        }
+    }
+}
+
+/// Reads the Content of the Certificate-, Key- and Client-CA-File, in that Order
+fn read_files(files: &TlsServerConfig) -> Result<Vec<Vec<u8>>, TlsError> {
+    let read = |path: &Path| std::fs::read(path).map_err(|e| TlsError::Read(path.to_owned(), e));
+
+    let mut content = vec![read(&files.cert_file)?, read(&files.key_file)?];
+    if let Some(path) = files.client_ca_file.as_ref() {
+        content.push(read(path)?);
+    }
+    Ok(content)
+}
+
+/// Parses all the PEM-encoded Certificates
+fn parse_certs(path: &Path, pem: &[u8]) -> Result<Vec<rustls::Certificate>, TlsError> {
+    let certs = rustls_pemfile::certs(&mut &*pem)
+        .map_err(|e| TlsError::Read(path.to_owned(), e))?
+        .into_iter()
+        .map(rustls::Certificate)
+        .collect::<Vec<_>>();
+
+    if certs.is_empty() {
+        return Err(TlsError::NoCertificates(path.to_owned()));
+    }
+    Ok(certs)
+}
+
+/// Parses the first PEM-encoded private Key, in either the PKCS#1, PKCS#8 or SEC1 Format
+fn parse_key(path: &Path, pem: &[u8]) -> Result<rustls::PrivateKey, TlsError> {
+    use rustls_pemfile::Item;
+
+    rustls_pemfile::read_all(&mut &*pem)
+        .map_err(|e| TlsError::Read(path.to_owned(), e))?
+        .into_iter()
+        .find_map(|item| match item {
+            Item::RSAKey(key) | Item::PKCS8Key(key) | Item::ECKey(key) => {
+                Some(rustls::PrivateKey(key))
+            }
+            _ => None,
+        })
+        .ok_or_else(|| TlsError::NoPrivateKey(path.to_owned()))
+}
+
+/// Creates the rustls-Configuration from the already read Files
+fn build_server_config(
+    files: &TlsServerConfig,
+    content: &[Vec<u8>],
+) -> Result<rustls::ServerConfig, TlsError> {
+    let certs = parse_certs(&files.cert_file, &content[0])?;
+    let key = parse_key(&files.key_file, &content[1])?;
+
+    let builder = rustls::ServerConfig::builder().with_safe_defaults();
+    let builder = match (files.client_auth_type, files.client_ca_file.as_ref()) {
+        (ClientAuthType::NoClientCert, _) => builder.with_no_client_auth(),
+        (auth_type, None) => return Err(TlsError::MissingClientCa(auth_type)),
+        (auth_type, Some(path)) => {
+            let mut roots = rustls::RootCertStore::empty();
+            for cert in parse_certs(path, &content[2])? {
+                roots
+                    .add(&cert)
+                    .map_err(|_| TlsError::InvalidClientCa(path.clone()))?;
+            }
+
+            let verifier = match auth_type {
+                ClientAuthType::VerifyClientCertIfGiven => {
+                    rustls::server::AllowAnyAnonymousOrAuthenticatedClient::new(roots)
+                }
+                _ => rustls::server::AllowAnyAuthenticatedClient::new(roots),
+            };
+            builder.with_client_cert_verifier(verifier)
+        }
+    };
+
+    let mut config = builder
+        .with_single_cert(certs, key)
+        .map_err(TlsError::Rustls)?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(client_auth_type: ClientAuthType, client_ca_file: Option<&str>) -> TlsServerConfig {
+        let fixture = |name: &str| {
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("tests/fixtures")
+                .join(name)
+        };
+
+        TlsServerConfig {
+            cert_file: fixture("server-cert.pem"),
+            key_file: fixture("server-key.pem"),
+            client_auth_type,
+            client_ca_file: client_ca_file.map(fixture),
+        }
+    }
+
+    #[test]
+    fn loads_without_client_auth() {
+        assert!(ReloadingConfig::load(files(ClientAuthType::NoClientCert, None)).is_ok());
+    }
+
+    #[test]
+    fn verifies_client_certs() {
+        let files = files(
+            ClientAuthType::RequireAndVerifyClientCert,
+            Some("server-cert.pem"),
+        );
+        assert!(ReloadingConfig::load(files).is_ok());
+    }
+
+    #[test]
+    fn requires_client_ca_for_verification() {
+        for auth_type in [
+            ClientAuthType::VerifyClientCertIfGiven,
+            ClientAuthType::RequireAndVerifyClientCert,
+        ] {
+            assert!(matches!(
+                ReloadingConfig::load(files(auth_type, None)),
+                Err(TlsError::MissingClientCa(t)) if t == auth_type
+            ));
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! The Configuration of the Webserver itself, in the same Format as the `web-config.yml` of
+//! the official Prometheus Exporters
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+/// The Error returned when the Web-Configuration could not be loaded
+#[derive(Debug)]
+pub enum WebConfigError {
+    /// The Web-Configuration-File could not be read
+    Read(PathBuf, std::io::Error),
+    /// The Web-Configuration-File could not be parsed
+    Yaml(serde_yaml::Error),
+    /// The Web-Configuration was loaded, but contains invalid Values
+    Invalid(Vec<String>),
+}
+
+impl Display for WebConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(path, e) => write!(f, "Reading {:?}: {}", path, e),
+            Self::Yaml(e) => write!(f, "Parsing YAML: {}", e),
+            Self::Invalid(errors) => {
+                writeln!(f, "The Web-Configuration is invalid:")?;
+                for error in errors {
+                    writeln!(f, "  - {}", error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for WebConfigError {}
+
+/// The Configuration of TLS and Authentication for the Webserver
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebConfig {
+    /// Serves everything over HTTPS instead of plain HTTP, if set
+    pub tls_server_config: Option<TlsServerConfig>,
+    /// The Users that are allowed to access the Webserver using Basic-Auth, mapped to the
+    /// bcrypt-Hash of their Password
+    pub basic_auth_users: BTreeMap<String, String>,
+    /// The Tokens that are allowed to access the Webserver using an
+    /// `Authorization: Bearer <token>` Header
+    pub bearer_tokens: Vec<String>,
+}
+
+/// The TLS-Configuration of the Webserver
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TlsServerConfig {
+    /// The Path to the PEM-encoded Certificate (Chain) of the Server
+    pub cert_file: PathBuf,
+    /// The Path to the PEM-encoded private Key of the Server
+    pub key_file: PathBuf,
+    /// Whether and how the Certificates of the Clients are verified
+    #[serde(default)]
+    pub client_auth_type: ClientAuthType,
+    /// The Path to the PEM-encoded CA-Certificates against which the Certificates of the
+    /// Clients are verified
+    #[serde(default)]
+    pub client_ca_file: Option<PathBuf>,
+}
+
+/// Whether and how the Certificates of the Clients are verified, using the same Names as Go's
+/// `tls.ClientAuthType`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ClientAuthType {
+    /// The Clients are not asked for a Certificate
+    #[default]
+    NoClientCert,
+    /// A Certificate is verified if the Client sends one, but it is not required
+    VerifyClientCertIfGiven,
+    /// Every Client has to send a valid Certificate
+    RequireAndVerifyClientCert,
+}
+
+impl WebConfig {
+    /// Loads the Web-Configuration from the given YAML-File and validates it
+    pub fn from_file(path: &Path) -> Result<Self, WebConfigError> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| WebConfigError::Read(path.to_owned(), e))?;
+        let config: Self = serde_yaml::from_str(&content).map_err(WebConfigError::Yaml)?;
+
+        let errors = config.validate();
+        if !errors.is_empty() {
+            return Err(WebConfigError::Invalid(errors));
+        }
+        Ok(config)
+    }
+
+    /// Checks the Web-Configuration for invalid Values, returning a Description of every Problem
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if let Some(tls) = self.tls_server_config.as_ref() {
+            if !tls.cert_file.is_file() {
+                errors.push(format!(
+                    "tls_server_config.cert_file: {:?} is not a File",
+                    tls.cert_file
+                ));
+            }
+            if !tls.key_file.is_file() {
+                errors.push(format!(
+                    "tls_server_config.key_file: {:?} is not a File",
+                    tls.key_file
+                ));
+            }
+            match (tls.client_auth_type, tls.client_ca_file.as_ref()) {
+                (_, Some(path)) if !path.is_file() => errors.push(format!(
+                    "tls_server_config.client_ca_file: {:?} is not a File",
+                    path
+                )),
+                (ClientAuthType::NoClientCert, _) | (_, Some(_)) => {}
+                (other, None) => errors.push(format!(
+                    "tls_server_config.client_ca_file: Required for {:?}",
+                    other
+                )),
+            }
+        }
+
+        for (user, hash) in self.basic_auth_users.iter() {
+            if user.is_empty() || user.contains(':') {
+                errors.push(format!("basic_auth_users: Invalid User {:?}", user));
+            }
+            // Only the bcrypt-Hashes are supported, a plain Password would never match
+            if !hash.starts_with("$2") {
+                errors.push(format!(
+                    "basic_auth_users.{}: The Password is not a bcrypt-Hash",
+                    user
+                ));
+            }
+        }
+        for (index, token) in self.bearer_tokens.iter().enumerate() {
+            if token.trim().is_empty() {
+                errors.push(format!("bearer_tokens.{}: The Token is empty", index));
+            }
+        }
+
+        errors
+    }
+}
@@ -0,0 +1,361 @@
+mod common;
+
+use std::{collections::BTreeSet, sync::Arc};
+
+use common::{droplet, MockResponse, MockServer};
+use prometheus::Registry;
+use rustyocean::{Collector, Exporter};
+use serde_json::{json, Value};
+
+/// A single Page containing all the given Items under the given Key
+fn list(key: &str, items: Value) -> MockResponse {
+    let total = items.as_array().map(Vec::len).unwrap_or_default();
+    MockResponse::json(json!({ key: items, "meta": { "total": total }, "links": {} }))
+}
+
+/// Serves a single Item of every Resource, with all the optional Fields set so every Metric
+/// gets a Series
+fn handler(path: &str) -> MockResponse {
+    let path = path.split('?').next().unwrap_or_default();
+
+    if path.starts_with("/monitoring/metrics/droplet/") {
+        return MockResponse::json(json!({
+            "data": { "result": [{
+                "metric": { "mode": "idle", "device": "/dev/vda1", "mountpoint": "/" },
+                "values": [[1, "1"], [2, "2"]]
+            }] }
+        }));
+    }
+
+    match path {
+        "/account" => MockResponse::json(json!({
+            "account": {
+                "droplet_limit": 25,
+                "email": "ops@example.com",
+                "email_verified": true,
+                "floating_ip_limit": 3,
+                "status": "active",
+                "uuid": "b6fr89dbf6d9156cace5f3c78dc9851d957381ef",
+                "volume_limit": 100
+            }
+        })),
+        "/customers/my/balance" => MockResponse::json(json!({
+            "account_balance": "12.34",
+            "generated_at": "2021-01-01T00:00:00Z",
+            "month_to_date_balance": "23.45",
+            "month_to_date_usage": "11.11"
+        })),
+        "/droplets" => list("droplets", json!([droplet(1)])),
+        "/floating_ips" => list(
+            "floating_ips",
+            json!([{ "ip": "45.55.96.47", "region": { "name": "Frankfurt 1", "slug": "fra1" } }]),
+        ),
+        "/vpcs" => list(
+            "vpcs",
+            json!([{
+                "name": "default-fra1",
+                "description": "",
+                "region": "fra1",
+                "ip_range": "10.114.0.0/20",
+                "default": true,
+                "id": "5a4981aa-9653-4bd1-bef5-d6bff52042e4",
+                "urn": "do:vpc:5a4981aa-9653-4bd1-bef5-d6bff52042e4",
+                "created_at": "2021-01-01T00:00:00Z"
+            }]),
+        ),
+        "/cdn/endpoints" => list(
+            "endpoints",
+            json!([{
+                "id": "19f06b6a-3ace-4315-b086-499a0e521b76",
+                "origin": "static.nyc3.digitaloceanspaces.com",
+                "endpoint": "static.nyc3.cdn.digitaloceanspaces.com",
+                "ttl": 3600,
+                "certificate_id": "",
+                "custom_domain": "",
+                "created_at": "2021-01-01T00:00:00Z"
+            }]),
+        ),
+        "/volumes" => list(
+            "volumes",
+            json!([{
+                "id": "506f78a4-e098-11e5-ad9f-000f53306ae1",
+                "name": "data",
+                "region": { "name": "Frankfurt 1", "slug": "fra1" },
+                "droplet_ids": [1],
+                "size_gigabytes": 10,
+                "filesystem_type": "ext4",
+                "created_at": "2021-01-01T00:00:00Z"
+            }]),
+        ),
+        "/kubernetes/clusters" => list(
+            "kubernetes_clusters",
+            json!([{
+                "id": "bd5f5959-5e1e-4205-a714-a914373942af",
+                "name": "prod",
+                "region": "fra1",
+                "version": "1.21.3-do.0",
+                "auto_upgrade": true,
+                "ha": true,
+                "status": { "state": "running" },
+                "node_pools": [{
+                    "id": "cdda885e-7663-40c8-bc74-3a036c66545d",
+                    "name": "workers",
+                    "size": "s-2vcpu-4gb",
+                    "count": 1,
+                    "auto_scale": true,
+                    "min_nodes": 1,
+                    "max_nodes": 3,
+                    "nodes": [{
+                        "id": "478247f8-b1bb-4f7a-8db9-2a5f8d4b8f8f",
+                        "name": "workers-1",
+                        "status": { "state": "running" },
+                        "droplet_id": "1"
+                    }]
+                }],
+                "created_at": "2021-01-01T00:00:00Z"
+            }]),
+        ),
+        "/kubernetes/clusters/bd5f5959-5e1e-4205-a714-a914373942af/upgrades" => {
+            MockResponse::json(json!({
+                "available_upgrade_versions": [
+                    { "slug": "1.22.2-do.0", "kubernetes_version": "1.22.2" }
+                ]
+            }))
+        }
+        "/databases" => list(
+            "databases",
+            json!([{
+                "id": "9cc10173-e9ea-4176-9dbc-a4cee4c4ff30",
+                "name": "backend",
+                "engine": "pg",
+                "version": "13",
+                "status": "online",
+                "num_nodes": 2,
+                "size": "db-s-1vcpu-1gb",
+                "region": "fra1",
+                "storage_size_mib": 10240,
+                "maintenance_window": { "day": "tuesday", "hour": "01:00:00", "pending": true },
+                "users": [{ "name": "doadmin", "role": "primary" }],
+                "created_at": "2021-01-01T00:00:00Z"
+            }]),
+        ),
+        "/databases/9cc10173-e9ea-4176-9dbc-a4cee4c4ff30/replicas" => MockResponse::json(json!({
+            "replicas": [{ "name": "read-replica", "region": "fra1", "status": "online" }]
+        })),
+        "/databases/9cc10173-e9ea-4176-9dbc-a4cee4c4ff30/pools" => MockResponse::json(json!({
+            "pools": [{ "name": "backend", "mode": "transaction", "size": 10, "db": "defaultdb" }]
+        })),
+        "/load_balancers" => list(
+            "load_balancers",
+            json!([{
+                "id": "4de7ac8b-495b-4884-9a69-1050c6793cd6",
+                "name": "web",
+                "ip": "104.131.186.241",
+                "size_unit": 1,
+                "algorithm": "round_robin",
+                "status": "active",
+                "forwarding_rules": [{
+                    "entry_protocol": "http",
+                    "entry_port": 80,
+                    "target_protocol": "http",
+                    "target_port": 80
+                }],
+                "droplet_ids": [1],
+                "region": { "name": "Frankfurt 1", "slug": "fra1" },
+                "created_at": "2021-01-01T00:00:00Z"
+            }]),
+        ),
+        "/domains" => list("domains", json!([{ "name": "example.com", "ttl": 1800 }])),
+        "/domains/example.com/records" => list(
+            "domain_records",
+            json!([{ "id": 28448429, "type": "A", "name": "@", "data": "1.2.3.4", "ttl": 1800 }]),
+        ),
+        "/certificates" => list(
+            "certificates",
+            json!([{
+                "id": "892071a0-bb95-49bc-8021-3afd67a210bf",
+                "name": "web",
+                "not_after": "2030-01-01T00:00:00Z",
+                "sha1_fingerprint": "dfcc9f57d86bf58e321c2c6c31c7a971be244ac7",
+                "dns_names": ["example.com"],
+                "state": "verified",
+                "type": "lets_encrypt",
+                "created_at": "2021-01-01T00:00:00Z"
+            }]),
+        ),
+        "/firewalls" => list(
+            "firewalls",
+            json!([{
+                "id": "bb4b2611-3d72-467b-8602-280330ecd65c",
+                "name": "web",
+                "status": "succeeded",
+                "inbound_rules": [{ "protocol": "tcp", "ports": "80" }],
+                "outbound_rules": [{ "protocol": "tcp", "ports": "all" }],
+                "droplet_ids": [1],
+                "tags": ["web"],
+                "created_at": "2021-01-01T00:00:00Z"
+            }]),
+        ),
+        _ => MockResponse::status(404),
+    }
+}
+
+/// The Metrics of all the Collectors, which should all have at least one Series
+const METRICS: &[&str] = &[
+    "droplet_limit",
+    "floating_ip_limit",
+    "volume_limit",
+    "account_balance",
+    "month_to_date_balance",
+    "month_to_date_usage",
+    "droplet_up",
+    "droplet_vcpus",
+    "droplet_memory",
+    "droplet_disk",
+    "droplet_transfer",
+    "droplet_price_monthly",
+    "droplet_price_hourly",
+    "floating_ip_info",
+    "vpc_info",
+    "cdn_endpoint_info",
+    "volume_info",
+    "volume_size_bytes",
+    "volume_attachments",
+    "volume_droplet",
+    "kubernetes_cluster_info",
+    "kubernetes_cluster_up",
+    "kubernetes_cluster_auto_upgrade",
+    "kubernetes_cluster_ha",
+    "kubernetes_cluster_upgrade_available",
+    "kubernetes_node_pool_nodes",
+    "kubernetes_node_pool_auto_scale",
+    "kubernetes_node_pool_min_nodes",
+    "kubernetes_node_pool_max_nodes",
+    "kubernetes_node_up",
+    "database_info",
+    "database_up",
+    "database_nodes",
+    "database_storage_size_bytes",
+    "database_maintenance_window",
+    "database_maintenance_pending",
+    "database_users",
+    "database_replicas",
+    "database_connection_pools",
+    "load_balancer_info",
+    "load_balancer_up",
+    "load_balancer_size_unit",
+    "load_balancer_forwarding_rules",
+    "load_balancer_droplet",
+    "domain_ttl",
+    "domain_records",
+    "domain_record_info",
+    "certificate_info",
+    "certificate_not_after_seconds",
+    "firewall_info",
+    "firewall_inbound_rules",
+    "firewall_outbound_rules",
+    "firewall_droplet",
+    "firewall_tag",
+    "droplet_firewalls",
+    "droplet_cpu_seconds",
+    "droplet_memory_available_bytes",
+    "droplet_memory_total_bytes",
+    "droplet_load1",
+    "droplet_filesystem_free_bytes",
+    "droplet_bandwidth_mbps",
+];
+
+#[tokio::test]
+async fn updates_every_metric() {
+    let server = MockServer::start(handler);
+    let collectors = Collector::ALL
+        .iter()
+        .map(|c| (*c, c.default_interval()))
+        .collect();
+    let exporter = Exporter::new(Arc::new(server.api()), collectors);
+    let registry = Registry::new();
+    exporter.register(&registry).unwrap();
+
+    for collector in Collector::ALL.iter() {
+        assert!(
+            exporter.run(*collector).await,
+            "Running {}: {:?}",
+            collector.name(),
+            exporter.status()
+        );
+    }
+
+    let families = registry.gather();
+    let gathered: BTreeSet<_> = families.iter().map(|f| f.get_name()).collect();
+    let missing: Vec<_> = METRICS
+        .iter()
+        .filter(|name| !gathered.contains(*name))
+        .collect();
+    assert!(missing.is_empty(), "Missing Metrics: {:?}", missing);
+
+    // Every Series carries the Account-Label, which is added to all Metrics of an Exporter
+    for family in families.iter() {
+        for metric in family.get_metric() {
+            assert!(
+                metric
+                    .get_label()
+                    .iter()
+                    .any(|l| l.get_name() == "account" && l.get_value() == "default"),
+                "{} is missing the account Label",
+                family.get_name()
+            );
+        }
+    }
+}
+
+#[tokio::test]
+async fn registers_multiple_accounts() {
+    let server = MockServer::start(handler);
+    let registry = Registry::new();
+
+    for name in ["production", "staging"] {
+        let api = rustyocean::api::API::builder("test-token")
+            .name(name)
+            .base_url(server.url())
+            .build()
+            .unwrap();
+        let exporter = Exporter::new(
+            Arc::new(api),
+            vec![(Collector::Droplets, Collector::Droplets.default_interval())],
+        );
+        exporter.register(&registry).unwrap();
+        assert!(exporter.run(Collector::Droplets).await);
+    }
+
+    let families = registry.gather();
+    let up = families
+        .iter()
+        .find(|f| f.get_name() == "droplet_up")
+        .unwrap();
+    assert_eq!(up.get_metric().len(), 2);
+}
+
+#[tokio::test]
+async fn firewalls_load_missing_droplets() {
+    let server = MockServer::start(handler);
+    let exporter = Exporter::new(
+        Arc::new(server.api()),
+        vec![(
+            Collector::Firewalls,
+            Collector::Firewalls.default_interval(),
+        )],
+    );
+    let registry = Registry::new();
+    exporter.register(&registry).unwrap();
+
+    assert!(exporter.run(Collector::Firewalls).await);
+    assert!(server.requests().iter().any(|r| r.starts_with("/droplets")));
+
+    let families = registry.gather();
+    let firewalls = families
+        .iter()
+        .find(|f| f.get_name() == "droplet_firewalls")
+        .unwrap();
+    assert_eq!(firewalls.get_metric().len(), 1);
+    assert_eq!(firewalls.get_metric()[0].get_gauge().get_value(), 1.0);
+}
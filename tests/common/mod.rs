@@ -0,0 +1,152 @@
+//! A minimal HTTP-Server that stands in for the DigitalOcean API in the Tests
+
+#![allow(dead_code)]
+
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server, StatusCode,
+};
+use rustyocean::api::API;
+
+/// The Response the Mock-Server sends for a single Request
+pub struct MockResponse {
+    /// The StatusCode of the Response
+    pub status: u16,
+    /// The Body of the Response
+    pub body: String,
+    /// Additional Headers of the Response
+    pub headers: Vec<(&'static str, String)>,
+}
+
+impl MockResponse {
+    /// A successful Response with the given JSON-Body
+    pub fn json(body: serde_json::Value) -> Self {
+        Self {
+            status: 200,
+            body: body.to_string(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// An empty Response with the given StatusCode
+    pub fn status(status: u16) -> Self {
+        Self {
+            status,
+            body: String::new(),
+            headers: Vec::new(),
+        }
+    }
+}
+
+/// A running Mock-Server, which answers every Request using its Handler
+pub struct MockServer {
+    /// The Address the Server is listening on
+    addr: SocketAddr,
+    /// The Path and Query of every Request received so far
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockServer {
+    /// Starts the Server on a random Port, the Handler receives the Path and Query of every
+    /// Request
+    pub fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&str) -> MockResponse + Send + Sync + 'static,
+    {
+        let handler = Arc::new(handler);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let server_requests = requests.clone();
+        let make_service = make_service_fn(move |_conn| {
+            let handler = handler.clone();
+            let requests = server_requests.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let path = req
+                        .uri()
+                        .path_and_query()
+                        .map(|p| p.to_string())
+                        .unwrap_or_default();
+                    requests.lock().unwrap().push(path.clone());
+
+                    let mock = handler(&path);
+                    let mut response = Response::builder()
+                        .status(StatusCode::from_u16(mock.status).unwrap())
+                        .header("content-type", "application/json");
+                    for (name, value) in mock.headers {
+                        response = response.header(name, value);
+                    }
+                    let response = response.body(Body::from(mock.body)).unwrap();
+                    async move { Ok::<_, Infallible>(response) }
+                }))
+            }
+        });
+
+        let server = Server::bind(&SocketAddr::from(([127, 0, 0, 1], 0))).serve(make_service);
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        Self { addr, requests }
+    }
+
+    /// The Base-URL under which the Server can be reached
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Creates an API-Client that sends all its Requests to this Server
+    pub fn api(&self) -> API {
+        API::builder("test-token")
+            .base_url(self.url())
+            .build()
+            .unwrap()
+    }
+
+    /// The Path and Query of every Request received so far
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+/// Extracts the Value of the given Parameter from the Query of a Request
+pub fn query_param<'a>(path: &'a str, name: &str) -> Option<&'a str> {
+    let query = path.split_once('?')?.1;
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// A running Droplet with the given ID, as returned by the API
+pub fn droplet(id: u64) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "name": format!("droplet-{}", id),
+        "memory": 1024,
+        "vcpus": 1,
+        "disk": 25,
+        "locked": false,
+        "status": "active",
+        "created_at": "2021-01-01T00:00:00Z",
+        "size": {
+            "slug": "s-1vcpu-1gb",
+            "memory": 1024,
+            "vcpus": 1,
+            "disk": 25,
+            "transfer": 1.0,
+            "price_monthly": 5.0,
+            "price_hourly": 0.00744,
+            "description": "Basic"
+        },
+        "region": { "name": "Frankfurt 1", "slug": "fra1" },
+        "networks": { "v4": [] },
+        "tags": ["web"]
+    })
+}
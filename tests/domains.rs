@@ -0,0 +1,61 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{MockResponse, MockServer};
+use prometheus::Registry;
+use rustyocean::{Collector, Exporter};
+use serde_json::json;
+
+/// Serves two Domains, failing the Records of the second one
+fn handler(path: &str) -> MockResponse {
+    let path = path.split('?').next().unwrap_or_default();
+    match path {
+        "/domains" => MockResponse::json(json!({
+            "domains": [
+                { "name": "example.com", "ttl": 1800 },
+                { "name": "example.org", "ttl": 3600 }
+            ],
+            "meta": { "total": 2 },
+            "links": {}
+        })),
+        "/domains/example.com/records" => MockResponse::json(json!({
+            "domain_records": [
+                { "id": 1, "type": "A", "name": "@", "data": "1.2.3.4", "ttl": 1800 }
+            ],
+            "meta": { "total": 1 },
+            "links": {}
+        })),
+        _ => MockResponse::status(404),
+    }
+}
+
+#[tokio::test]
+async fn reports_failed_records() {
+    let server = MockServer::start(handler);
+    let exporter = Exporter::new(
+        Arc::new(server.api()),
+        vec![(Collector::Domains, Collector::Domains.default_interval())],
+    );
+    let registry = Registry::new();
+    exporter.register(&registry).unwrap();
+
+    assert!(!exporter.run(Collector::Domains).await);
+
+    let (_, status) = exporter.status().pop().unwrap();
+    let error = status.unwrap().error.unwrap();
+    assert!(error.starts_with("Partial { failed: 1"), "{}", error);
+
+    // The Domain that could be loaded is still updated
+    let families = registry.gather();
+    let ttl = families
+        .iter()
+        .find(|f| f.get_name() == "domain_ttl")
+        .unwrap();
+    assert_eq!(ttl.get_metric().len(), 2);
+    let records = families
+        .iter()
+        .find(|f| f.get_name() == "domain_record_info")
+        .unwrap();
+    assert_eq!(records.get_metric().len(), 1);
+}
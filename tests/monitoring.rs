@@ -0,0 +1,66 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{droplet, MockResponse, MockServer};
+use prometheus::Registry;
+use rustyocean::{Collector, Exporter};
+use serde_json::json;
+
+/// Serves two Droplets, failing the `load_1` Metric of the second one
+fn handler(path: &str) -> MockResponse {
+    if path.starts_with("/droplets") {
+        return MockResponse::json(json!({
+            "droplets": [droplet(1), droplet(2)],
+            "meta": { "total": 2 },
+            "links": {}
+        }));
+    }
+    if path.starts_with("/monitoring/metrics/droplet/load_1") && path.contains("host_id=2") {
+        return MockResponse::status(404);
+    }
+    MockResponse::json(json!({
+        "data": { "result": [{ "metric": {}, "values": [[1, "0.5"]] }] }
+    }))
+}
+
+#[tokio::test]
+async fn reports_failed_requests() {
+    let server = MockServer::start(handler);
+    let exporter = Exporter::new(
+        Arc::new(server.api()),
+        vec![(
+            Collector::Monitoring,
+            Collector::Monitoring.default_interval(),
+        )],
+    );
+    let registry = Registry::new();
+    exporter.register(&registry).unwrap();
+
+    assert!(!exporter.run(Collector::Monitoring).await);
+
+    let (_, status) = exporter.status().pop().unwrap();
+    let error = status.unwrap().error.unwrap();
+    assert!(error.starts_with("Partial { failed: 1"), "{}", error);
+
+    // The Metrics that could be loaded are still updated
+    let families = registry.gather();
+    let load1 = families
+        .iter()
+        .find(|f| f.get_name() == "droplet_load1")
+        .unwrap();
+    assert_eq!(load1.get_metric().len(), 1);
+    let memory = families
+        .iter()
+        .find(|f| f.get_name() == "droplet_memory_total_bytes")
+        .unwrap();
+    assert_eq!(memory.get_metric().len(), 2);
+
+    // Every Metric is requested once for every Droplet
+    let monitoring_requests = server
+        .requests()
+        .iter()
+        .filter(|r| r.starts_with("/monitoring"))
+        .count();
+    assert_eq!(monitoring_requests, 18);
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::{query_param, MockResponse, MockServer};
+use rustyocean::api::GetResouceError;
+use serde_json::json;
+
+/// Serves the given Pages of Items under `things`, linking every Page to the next one
+fn paged(pages: Vec<Vec<u64>>, total: u64) -> impl Fn(&str) -> MockResponse {
+    move |path| {
+        let page: usize = query_param(path, "page").unwrap().parse().unwrap();
+        let items = pages.get(page - 1).cloned().unwrap_or_default();
+
+        let mut body = json!({ "things": items, "meta": { "total": total }, "links": {} });
+        if page < pages.len() {
+            body["links"] = json!({ "pages": { "next": format!("/things?page={}", page + 1) } });
+        }
+        MockResponse::json(body)
+    }
+}
+
+#[tokio::test]
+async fn follows_all_pages() {
+    let server = MockServer::start(paged(vec![vec![1, 2], vec![3, 4], vec![5]], 5));
+
+    let items: Vec<u64> = server.api().get_list("/things", "things").await.unwrap();
+
+    assert_eq!(items, vec![1, 2, 3, 4, 5]);
+    assert_eq!(
+        server.requests(),
+        vec![
+            "/things?page=1&per_page=200",
+            "/things?page=2&per_page=200",
+            "/things?page=3&per_page=200",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn keeps_existing_query() {
+    let server = MockServer::start(paged(vec![vec![1]], 1));
+
+    let items: Vec<u64> = server
+        .api()
+        .get_list("/things?tag_name=web", "things")
+        .await
+        .unwrap();
+
+    assert_eq!(items, vec![1]);
+    assert_eq!(
+        server.requests(),
+        vec!["/things?tag_name=web&page=1&per_page=200"]
+    );
+}
+
+#[tokio::test]
+async fn stops_at_empty_page() {
+    // The next Link is only missing on the Page after the last one
+    let server = MockServer::start(paged(vec![vec![1], vec![], vec![]], 1));
+
+    let items: Vec<u64> = server.api().get_list("/things", "things").await.unwrap();
+
+    assert_eq!(items, vec![1]);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn rejects_total_mismatch() {
+    let server = MockServer::start(paged(vec![vec![1, 2], vec![3]], 10));
+
+    let result = server.api().get_list::<u64>("/things", "things").await;
+
+    assert!(matches!(
+        result,
+        Err(GetResouceError::TotalMismatch {
+            expected: 10,
+            received: 3
+        })
+    ));
+}
+
+#[tokio::test]
+async fn rejects_missing_list() {
+    let server = MockServer::start(|_| MockResponse::json(json!({ "meta": { "total": 0 } })));
+
+    let result = server.api().get_list::<u64>("/things", "things").await;
+
+    assert!(matches!(result, Err(GetResouceError::MissingData)));
+}
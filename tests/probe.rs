@@ -0,0 +1,36 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{MockResponse, MockServer};
+use rustyocean::{api::GetError, Collector, Exporter};
+use serde_json::json;
+
+#[tokio::test]
+async fn probes_with_single_request() {
+    let server = MockServer::start(|path| {
+        if path.starts_with("/monitoring") {
+            MockResponse::status(403)
+        } else {
+            MockResponse::json(json!({}))
+        }
+    });
+    let exporter = Exporter::new(Arc::new(server.api()), Vec::new());
+
+    exporter.probe(Collector::Account).await.unwrap();
+    exporter.probe(Collector::Droplets).await.unwrap();
+    let result = exporter.probe(Collector::Monitoring).await;
+
+    assert!(matches!(
+        result,
+        Err(GetError::StatusCode(status)) if status.as_u16() == 403
+    ));
+    assert_eq!(
+        server.requests(),
+        vec![
+            "/account",
+            "/droplets?per_page=1",
+            "/monitoring/alerts?per_page=1"
+        ]
+    );
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use common::{MockResponse, MockServer};
+use rustyocean::api::{GetError, API};
+use serde_json::json;
+
+/// Fails the first Requests with the given StatusCode and succeeds afterwards
+fn fail_first(failures: usize, status: u16) -> impl Fn(&str) -> MockResponse {
+    let count = AtomicUsize::new(0);
+    move |_| {
+        if count.fetch_add(1, Ordering::SeqCst) < failures {
+            MockResponse::status(status)
+        } else {
+            MockResponse::json(json!({ "ok": true }))
+        }
+    }
+}
+
+#[tokio::test]
+async fn retries_rate_limited_requests() {
+    let server = MockServer::start(fail_first(2, 429));
+
+    let body = server.api().get("/account").await.unwrap();
+
+    assert_eq!(body, json!({ "ok": true }));
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn retries_server_errors() {
+    let server = MockServer::start(fail_first(1, 503));
+
+    server.api().get("/account").await.unwrap();
+
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn does_not_retry_client_errors() {
+    let server = MockServer::start(fail_first(1, 403));
+
+    let result = server.api().get("/account").await;
+
+    assert!(matches!(result, Err(GetError::StatusCode(s)) if s.as_u16() == 403));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn records_rate_limit() {
+    let server = MockServer::start(|_| {
+        let mut response = MockResponse::json(json!({}));
+        response.headers = vec![
+            ("ratelimit-limit", "5000".to_string()),
+            ("ratelimit-remaining", "4321".to_string()),
+            ("ratelimit-reset", "1700000000".to_string()),
+        ];
+        response
+    });
+    let api = server.api();
+
+    api.get("/account").await.unwrap();
+
+    let rate_limit = api.rate_limit().unwrap();
+    assert_eq!(rate_limit.limit, 5000);
+    assert_eq!(rate_limit.remaining, 4321);
+    assert_eq!(rate_limit.reset, 1700000000);
+}
+
+#[tokio::test]
+async fn times_out_hanging_requests() {
+    // Accepts Connections, but never responds to them
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut connections = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            connections.push(stream);
+        }
+    });
+
+    let api = API::builder("test-token")
+        .base_url(format!("http://{}", addr))
+        .timeout(Duration::from_millis(100))
+        .build()
+        .unwrap();
+    let result = tokio::time::timeout(Duration::from_secs(5), api.get("/account")).await;
+
+    assert!(matches!(result, Ok(Err(GetError::Reqwest(e))) if e.is_timeout()));
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use std::{sync::Arc, time::Duration};
+
+use common::{droplet, MockResponse, MockServer};
+use prometheus::Registry;
+use rustyocean::{scrape::ScrapeCollector, Collector, Exporter};
+use serde_json::json;
+
+/// Serves a single Droplet
+fn handler(_: &str) -> MockResponse {
+    MockResponse::json(json!({
+        "droplets": [droplet(1)],
+        "meta": { "total": 1 },
+        "links": {}
+    }))
+}
+
+fn scraper(server: &MockServer, interval: Duration, cache_ttl: Duration) -> ScrapeCollector {
+    let exporter = Exporter::new(
+        Arc::new(server.api()),
+        vec![(Collector::Droplets, interval)],
+    );
+    ScrapeCollector::new(Arc::new(exporter), cache_ttl)
+}
+
+/// Runs on the current-thread Runtime, on which blocking while collecting is not possible
+#[tokio::test]
+async fn collects_last_refresh() {
+    let server = MockServer::start(handler);
+    let scraper = scraper(&server, Duration::ZERO, Duration::from_secs(60));
+    let registry = Registry::new();
+    registry.register(Box::new(scraper.clone())).unwrap();
+
+    // Gathering alone never loads anything
+    assert!(registry.gather().is_empty());
+    assert!(server.requests().is_empty());
+
+    scraper.refresh().await;
+    let families = registry.gather();
+    assert!(families.iter().any(|f| f.get_name() == "droplet_up"));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn reuses_metrics_within_ttl() {
+    let server = MockServer::start(handler);
+    let cached = scraper(&server, Duration::ZERO, Duration::from_secs(60));
+
+    cached.refresh().await;
+    cached.clone().refresh().await;
+    assert_eq!(server.requests().len(), 1);
+
+    let expiring = scraper(&server, Duration::ZERO, Duration::from_millis(1));
+    expiring.refresh().await;
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    expiring.refresh().await;
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn waits_for_collector_interval() {
+    let server = MockServer::start(handler);
+    let scraper = scraper(&server, Duration::from_secs(60), Duration::from_millis(1));
+
+    scraper.refresh().await;
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    scraper.refresh().await;
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn coalesces_concurrent_scrapes() {
+    let server = MockServer::start(handler);
+    let scraper = scraper(&server, Duration::ZERO, Duration::from_secs(60));
+    let registry = Arc::new(Registry::new());
+    registry.register(Box::new(scraper.clone())).unwrap();
+
+    let scrapes: Vec<_> = (0..8)
+        .map(|_| {
+            let scraper = scraper.clone();
+            let registry = registry.clone();
+            tokio::spawn(async move {
+                scraper.refresh().await;
+                registry.gather()
+            })
+        })
+        .collect();
+    for scrape in scrapes {
+        let families = scrape.await.unwrap();
+        assert!(families.iter().any(|f| f.get_name() == "droplet_up"));
+    }
+
+    assert_eq!(server.requests().len(), 1);
+}